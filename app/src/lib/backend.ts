@@ -6,21 +6,32 @@
  * simplifying the architecture to a single code path.
  */
 
-import type { FrontendState, GameSettings, SoundEvent, GameVariant } from './game-store.svelte';
+import type { Action, EngineEvent, FrontendState, GameSettings, GameVariant, Profile } from './game-store.svelte';
 
 // Backend interface
 export interface IBackendAdapter {
     getGameState(): Promise<FrontendState>;
     getSettings(): Promise<GameSettings>;
+    dispatch(action: Action): Promise<FrontendState>;
     updateSettings(settings: GameSettings): Promise<FrontendState>;
     goToSettings(): Promise<FrontendState>;
     goToAbout(): Promise<FrontendState>;
-    startNewGame(variant: GameVariant): Promise<FrontendState>;
+    goToProgress(): Promise<FrontendState>;
+    startNewGame(variant: GameVariant, seed?: number): Promise<FrontendState>;
     submitAnswer(answer: string): Promise<FrontendState>;
     nextLevel(): Promise<FrontendState>;
+    requestHint(): Promise<FrontendState>;
+    replayPrompt(): Promise<FrontendState>;
+    pauseGame(): Promise<FrontendState>;
+    resumeGame(): Promise<FrontendState>;
     resetGame(): Promise<FrontendState>;
-    consumeSound(): Promise<SoundEvent | null>;
+    drainEvents(): Promise<EngineEvent[]>;
     tick(dt: number): Promise<FrontendState>;
+    listProfiles(): Promise<Profile[]>;
+    createProfile(name: string, avatar: string): Promise<FrontendState>;
+    selectProfile(id: string): Promise<FrontendState>;
+    deleteProfile(id: string): Promise<Profile[]>;
+    switchProfile(): Promise<FrontendState>;
 }
 
 // WASM Backend - runs Rust code via WebAssembly (used for ALL platforms)
@@ -54,6 +65,11 @@ class WasmBackend implements IBackendAdapter {
         return engine.get_settings() as GameSettings;
     }
 
+    async dispatch(action: Action): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.dispatch(action) as FrontendState;
+    }
+
     async updateSettings(settings: GameSettings): Promise<FrontendState> {
         const engine = await this.ensureInitialized();
         return engine.update_settings(settings) as FrontendState;
@@ -69,14 +85,19 @@ class WasmBackend implements IBackendAdapter {
         return engine.go_to_about() as FrontendState;
     }
 
-    async startNewGame(variant: GameVariant): Promise<FrontendState> {
+    async goToProgress(): Promise<FrontendState> {
         const engine = await this.ensureInitialized();
-        return engine.start_new_game(variant) as FrontendState;
+        return engine.go_to_progress() as FrontendState;
+    }
+
+    async startNewGame(variant: GameVariant, seed?: number): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.start_new_game(variant, seed) as FrontendState;
     }
 
     async submitAnswer(answer: string): Promise<FrontendState> {
         const engine = await this.ensureInitialized();
-        return engine.submit_answer(answer.charAt(0)) as FrontendState;
+        return engine.submit_answer(answer) as FrontendState;
     }
 
     async nextLevel(): Promise<FrontendState> {
@@ -84,21 +105,65 @@ class WasmBackend implements IBackendAdapter {
         return engine.next_level() as FrontendState;
     }
 
+    async requestHint(): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.request_hint() as FrontendState;
+    }
+
+    async replayPrompt(): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.replay_prompt() as FrontendState;
+    }
+
+    async pauseGame(): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.pause_game() as FrontendState;
+    }
+
+    async resumeGame(): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.resume_game() as FrontendState;
+    }
+
     async resetGame(): Promise<FrontendState> {
         const engine = await this.ensureInitialized();
         return engine.reset_game() as FrontendState;
     }
 
-    async consumeSound(): Promise<SoundEvent | null> {
+    async drainEvents(): Promise<EngineEvent[]> {
         const engine = await this.ensureInitialized();
-        const sound = engine.consume_sound();
-        return sound === null ? null : (sound as SoundEvent);
+        return engine.drain_events() as EngineEvent[];
     }
 
     async tick(dt: number): Promise<FrontendState> {
         const engine = await this.ensureInitialized();
         return engine.tick(dt) as FrontendState;
     }
+
+    async listProfiles(): Promise<Profile[]> {
+        const engine = await this.ensureInitialized();
+        return engine.list_profiles() as Profile[];
+    }
+
+    async createProfile(name: string, avatar: string): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.create_profile(name, avatar) as FrontendState;
+    }
+
+    async selectProfile(id: string): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.select_profile(id) as FrontendState;
+    }
+
+    async deleteProfile(id: string): Promise<Profile[]> {
+        const engine = await this.ensureInitialized();
+        return engine.delete_profile(id) as Profile[];
+    }
+
+    async switchProfile(): Promise<FrontendState> {
+        const engine = await this.ensureInitialized();
+        return engine.switch_profile() as FrontendState;
+    }
 }
 
 // Singleton backend instance
@@ -1,63 +1,247 @@
 import { getBackend, type IBackendAdapter } from "./backend";
 
 // Define types locally matching Rust types
-export type GameVariant = "Numbers" | "Letters";
+export type GameVariant =
+    | "Numbers"
+    | "ZeroToTen"
+    | "Teens"
+    | "Letters"
+    | "Phonics"
+    | "Words"
+    | "SightWords"
+    | "Maths"
+    | "Counting"
+    | "Sequence"
+    | "Lowercase"
+    | "UpperToLower"
+    | "LowerToUpper"
+    // A pool named in GameSettings.custom_pools
+    | { Custom: string };
 export type InputMethod = "DirectKeyboard" | "ArrowSelection" | "Hybrid";
 
+export type DistractorKind = "Random" | "Visual" | "Phonetic" | "Phonemes" | "Nearby";
+
+export interface LevelSpec {
+    questions: number;
+    options: number;
+    time_limit: number | null;
+    pass_threshold: number;
+    boss: boolean;
+    distractors: DistractorKind;
+}
+
+export interface Curriculum {
+    levels: LevelSpec[];
+}
+
+export type QuestionSelection = "Uniform" | "SpacedRepetition";
+
+export type HintStyle = "RemoveOption" | "HighlightTarget" | "ReplayPrompt";
+
+export type RetryPolicy =
+    | "MoveOn"
+    | { Retry: { attempts: number } }
+    | { RetryWithFewerOptions: { attempts: number } };
+
+export type Language = "English" | "Spanish" | "German";
+
+// A catalogue key and its arguments; the engine renders it as FrontendState.feedback_text
+export interface Message {
+    key: string;
+    args: Record<string, string>;
+}
+
+export type Alphabet = "English" | "Welsh" | "Spanish" | "German" | "Greek" | "Russian";
+
 export interface GameSettings {
     feedback_duration_seconds: number;
     show_target_visual: boolean;
     input_method: InputMethod;
     start_level: number;
+    curriculum: Curriculum;
+    question_selection: QuestionSelection;
+    adaptive_difficulty: boolean;
+    custom_pools: Record<string, string[]>;
+    alphabet: Alphabet;
+    language: Language;
+    retry_policy: RetryPolicy;
+    hint_style: HintStyle;
+    hint_penalty: number;
+}
+
+export interface ProfileStats {
+    sessions_started: number;
+    sessions_completed: number;
+    questions_answered: number;
+    correct_answers: number;
+    best_score: number;
+    correct_after_retry: number;
+    hints_used: number;
+}
+
+export interface CharacterStats {
+    attempts: number;
+    correct: number;
+    total_response_time: number;
+}
+
+export interface Mastery {
+    characters: Record<string, CharacterStats>;
+    confusions: Record<string, Record<string, number>>;
+}
+
+export interface LeitnerCard {
+    box_index: number;
+    last_seen: number;
+}
+
+export interface Leitner {
+    cards: Record<string, LeitnerCard>;
+    clock: number;
+}
+
+export interface Profile {
+    id: string;
+    name: string;
+    avatar: string;
+    created_at: number;
+    settings: GameSettings | null;
+    stats: ProfileStats;
+    mastery: Mastery;
+    leitner: Leitner;
 }
 
+export type Action =
+    | { StartGame: GameVariant }
+    | { Submit: string }
+    | "SubmitSelection"
+    | { MoveSelection: number }
+    | "RequestHint"
+    | "ReplayPrompt"
+    | "Pause"
+    | "Resume"
+    | "Continue"
+    | { Tick: number }
+    | "Quit"
+    | "OpenSettings"
+    | { UpdateSettings: GameSettings }
+    | "UseDefaultSettings"
+    | { ShowSettingsMessage: string }
+    | "OpenAbout"
+    | "OpenProgress"
+    | { SelectProfile: Profile }
+    | "SwitchProfile";
+
 export type SoundEvent =
-    | "None"
     | "PlaySuccess"
     | "PlayFailure"
     | { SayPrompt: string }
+    | { SayMatchPrompt: string }
+    | { SayPhoneme: string }
+    | { SayWord: string }
     | "GameStart"
     | "LevelComplete";
 
+export type EngineEvent =
+    | { Sound: SoundEvent }
+    | { StatusChanged: GameStatus }
+    | { ScoreChanged: { score: number, total_score: number } }
+    | { TimerWarning: { seconds_left: number } }
+    | { ProfileUpdated: Profile };
+
 export type GameStatus =
+    | "ProfileSelect"
     | "Menu"
     | { Settings: { message: string | null } }
     | "Playing"
-    | { Feedback: { success: boolean, message: string } }
+    | { Feedback: { success: boolean, message: Message } }
+    | { Paused: { resume: GameStatus } }
     | { LevelComplete: { level: number, score: number, passed: boolean } }
     | { LevelComplete: { level: number, score: number, passed: boolean } }
     | { SessionComplete: { score: number } }
-    | "About";
+    | "About"
+    | "Progress";
+
+export interface AdaptiveDifficulty {
+    options_offset: number;
+    time_scale: number;
+}
+
+export type CountObject = "Star" | "Rocket" | "Moon";
+
+export type QuestionShape =
+    | "Choose"
+    | { Spell: { built: string[] } }
+    | { Count: { quantity: number; object: CountObject; columns: number } }
+    | { Sequence: { items: (string | null)[] } };
 
 export interface SessionState {
     variant: GameVariant;
+    seed: number;
     current_level: number;
+    total_levels: number;
+    is_boss_level: boolean;
     current_question_index: number;
     total_questions: number;
     score: number;
+    credit: number;
     total_score: number;
     target: string;
+    prompt: string;
     options: string[];
+    question: QuestionShape;
     level_time_limit: number | null;
     level_elapsed_time: number;
+    question_elapsed_time: number;
+    attempt: number;
+    hints_used: number;
+    highlighted: string | null;
+    prompt_replays: number;
+    last_replay_at: number | null;
+    adaptive: AdaptiveDifficulty | null;
 }
 
 export interface FrontendState {
     status: GameStatus;
     session: SessionState | null;
+    profile: Profile | null;
+    feedback_text: string | null;
 }
 
 // Svelte 5 Runes Store with Backend Adapter Pattern
 class GameStore {
-    state = $state<FrontendState>({ status: "Menu", session: null });
+    state = $state<FrontendState>({
+        status: "ProfileSelect",
+        session: null,
+        profile: null,
+        feedback_text: null
+    });
+    profiles = $state<Profile[]>([]);
     settings = $state<GameSettings>({
         feedback_duration_seconds: 2,
         show_target_visual: false,
         input_method: "Hybrid",
-        start_level: 1
+        start_level: 1,
+        curriculum: { levels: [] },
+        question_selection: "Uniform",
+        adaptive_difficulty: false,
+        custom_pools: {},
+        alphabet: "English",
+        language: "English",
+        retry_policy: "MoveOn",
+        hint_style: "RemoveOption",
+        hint_penalty: 0
     });
-    lastSound = $state<SoundEvent | null>(null);
+    // Why the last settings save was rejected, shown on the settings screen
+    settingsError = $state<string | null>(null);
+    // Sounds waiting to be played, oldest first
+    pendingSounds = $state<SoundEvent[]>([]);
+    // Seconds left at each TimerWarning not yet ticked, oldest first
+    pendingTicks = $state<number[]>([]);
     feedbackTimer: number | null = null;
+    // When the running feedback timer fires, and what was left of it at a pause
+    feedbackDeadline = 0;
+    feedbackRemaining: number | null = null;
 
     // Backend adapter - initialized lazily
     private backendPromise: Promise<IBackendAdapter>;
@@ -74,8 +258,13 @@ class GameStore {
             this.backend = await this.backendPromise;
             await this.syncState();
             await this.loadSettings();
-            // Poll for sound every 100ms
-            setInterval(() => this.checkSound(), 100);
+            // Poll for engine events every 100ms
+            setInterval(() => this.checkEvents(), 100);
+
+            // Pause when the tab is hidden so timers don't run out unseen
+            document.addEventListener("visibilitychange", () => {
+                if (document.hidden) this.pause();
+            });
 
             // Game Tick Loop (100ms)
             let lastTime = 0;
@@ -110,25 +299,36 @@ class GameStore {
         if (newState.status && typeof newState.status === 'object' && 'Feedback' in newState.status) {
             if (!this.feedbackTimer) {
                 // Start timer if not already running
-                // Use duration from settings
+                // Use duration from settings, or what was left when the game paused
                 let duration = this.settings.feedback_duration_seconds * 1000;
 
                 // For Boss levels (4+) on success, add extra time for the explosion animation
-                if (newState.session && newState.session.current_level >= 4 && newState.status.Feedback.success) {
+                if (newState.session && newState.session.is_boss_level && newState.status.Feedback.success) {
                     duration += 3000; // 3 extra seconds of glory
                 }
+                duration = this.feedbackRemaining ?? duration;
+                this.feedbackRemaining = null;
+                this.feedbackDeadline = Date.now() + duration;
 
                 this.feedbackTimer = setTimeout(() => {
                     this.nextLevel();
                     this.feedbackTimer = null;
                 }, duration);
             }
+        } else if (newState.status && typeof newState.status === 'object' && 'Paused' in newState.status) {
+            // Hold the rest of the feedback time until play resumes
+            if (this.feedbackTimer) {
+                clearTimeout(this.feedbackTimer);
+                this.feedbackTimer = null;
+                this.feedbackRemaining = Math.max(0, this.feedbackDeadline - Date.now());
+            }
         } else {
             // Clear timer if we moved away from Feedback
             if (this.feedbackTimer) {
                 clearTimeout(this.feedbackTimer);
                 this.feedbackTimer = null;
             }
+            this.feedbackRemaining = null;
         }
         this.state = newState;
     }
@@ -155,66 +355,138 @@ class GameStore {
     async saveSettings(newSettings: GameSettings) {
         try {
             const backend = await this.getBackendOrWait();
-            this.settings = newSettings;
             const newState = await backend.updateSettings(newSettings);
+            this.settings = newSettings;
+            this.settingsError = null;
             this.processState(newState);
         } catch (e) {
             console.error("Failed to save settings", e);
+            this.settingsError = e instanceof Error ? e.message : String(e);
         }
     }
 
+    // Drops the player's own settings and goes back to the menu on the device defaults
+    async useDefaultSettings() {
+        await this.apply((backend) => backend.dispatch("UseDefaultSettings"));
+        await this.apply((backend) => backend.dispatch("Quit"));
+        await this.loadSettings();
+    }
+
+    // Runs an engine call and applies the resulting state. The engine throws on
+    // invalid transitions (e.g. a double click after answering); log and carry on.
+    private async apply(call: (backend: IBackendAdapter) => Promise<FrontendState>) {
+        try {
+            const backend = await this.getBackendOrWait();
+            this.processState(await call(backend));
+        } catch (e) {
+            console.warn("Engine rejected action", e);
+        }
+    }
+
+    async loadProfiles() {
+        try {
+            const backend = await this.getBackendOrWait();
+            this.profiles = await backend.listProfiles();
+        } catch (e) {
+            console.error("Failed to load profiles", e);
+        }
+    }
+
+    async createProfile(name: string, avatar: string) {
+        await this.apply((backend) => backend.createProfile(name, avatar));
+        // The player's settings may differ from the device defaults
+        await this.loadSettings();
+    }
+
+    async selectProfile(id: string) {
+        await this.apply((backend) => backend.selectProfile(id));
+        await this.loadSettings();
+    }
+
+    async deleteProfile(id: string) {
+        try {
+            const backend = await this.getBackendOrWait();
+            this.profiles = await backend.deleteProfile(id);
+        } catch (e) {
+            console.error("Failed to delete profile", e);
+        }
+    }
+
+    async switchProfile() {
+        await this.apply((backend) => backend.switchProfile());
+        await this.loadSettings();
+    }
+
     async goToSettings() {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.goToSettings();
-        this.processState(newState);
+        this.settingsError = null;
+        await this.apply((backend) => backend.goToSettings());
     }
 
     async goToAbout() {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.goToAbout();
-        this.processState(newState);
+        await this.apply((backend) => backend.goToAbout());
     }
 
-    async checkSound() {
+    async goToProgress() {
+        await this.apply((backend) => backend.goToProgress());
+    }
+
+    async checkEvents() {
         try {
             const backend = await this.getBackendOrWait();
-            const sound = await backend.consumeSound();
-            if (sound) {
-                this.lastSound = sound;
+            const events = await backend.drainEvents();
+            const sounds = events.flatMap((e) => ("Sound" in e ? [e.Sound] : []));
+            if (sounds.length > 0) {
+                this.pendingSounds = [...this.pendingSounds, ...sounds];
+            }
+            const ticks = events.flatMap((e) =>
+                "TimerWarning" in e ? [e.TimerWarning.seconds_left] : [],
+            );
+            if (ticks.length > 0) {
+                this.pendingTicks = [...this.pendingTicks, ...ticks];
             }
         } catch (e) {
-            // Silently ignore - sound polling errors are not critical
+            // Silently ignore - event polling errors are not critical
         }
     }
 
-    async startGame(variant: GameVariant) {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.startNewGame(variant);
-        this.processState(newState);
+    async startGame(variant: GameVariant, seed?: number) {
+        await this.apply((backend) => backend.startNewGame(variant, seed));
     }
 
     async submitAnswer(answer: string) {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.submitAnswer(answer);
-        this.processState(newState);
+        await this.apply((backend) => backend.submitAnswer(answer));
     }
 
     async nextLevel() {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.nextLevel();
-        this.processState(newState);
+        await this.apply((backend) => backend.nextLevel());
+    }
+
+    async requestHint() {
+        await this.apply((backend) => backend.requestHint());
+    }
+
+    async replayPrompt() {
+        await this.apply((backend) => backend.replayPrompt());
+    }
+
+    // Only questions and their feedback can be paused
+    async pause() {
+        const status = this.state.status;
+        if (status === "Playing" || (typeof status === "object" && "Feedback" in status)) {
+            await this.apply((backend) => backend.pauseGame());
+        }
+    }
+
+    async resume() {
+        await this.apply((backend) => backend.resumeGame());
     }
 
     async reset() {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.resetGame();
-        this.processState(newState);
+        await this.apply((backend) => backend.resetGame());
     }
 
     async tick(dt: number) {
-        const backend = await this.getBackendOrWait();
-        const newState = await backend.tick(dt);
-        this.processState(newState);
+        await this.apply((backend) => backend.tick(dt));
     }
 }
 
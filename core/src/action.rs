@@ -0,0 +1,45 @@
+use crate::profile::Profile;
+use crate::settings::GameSettings;
+use crate::state::GameVariant;
+use serde::{Deserialize, Serialize};
+
+/// Everything a frontend can ask the engine to do. Sent through `GameEngine::dispatch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    /// Start a new session from the menu.
+    StartGame(GameVariant),
+    /// Answer the current question with a typed token, e.g. "B" or "14".
+    Submit(String),
+    /// Answer the current question with the highlighted option.
+    SubmitSelection,
+    /// Move the option highlight left (negative) or right (positive).
+    MoveSelection(i32),
+    /// Ask for help with the current question; what it does is set by `GameSettings::hint_style`.
+    RequestHint,
+    /// Say the current prompt again. Repeats within a second and a half are ignored.
+    ReplayPrompt,
+    /// Leave the feedback or level summary screen.
+    Continue,
+    /// Freeze timers and hide the question, e.g. when the window loses focus.
+    Pause,
+    /// Carry on exactly where `Pause` left off.
+    Resume,
+    /// Advance the level timer by the given number of seconds.
+    Tick(f64),
+    /// Leave the current screen (abandoning any session) and return to the menu.
+    Quit,
+    OpenSettings,
+    /// Replace the settings while on the menu or settings screen.
+    UpdateSettings(GameSettings),
+    /// Drop the active player's own settings so they follow the device defaults again.
+    UseDefaultSettings,
+    /// Show a status line on the settings screen (e.g. export results).
+    ShowSettingsMessage(String),
+    OpenAbout,
+    /// Show the active profile's progress report from the menu.
+    OpenProgress,
+    /// Choose who is playing from the profile picker.
+    SelectProfile(Profile),
+    /// Return from the menu to the profile picker.
+    SwitchProfile,
+}
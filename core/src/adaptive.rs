@@ -0,0 +1,162 @@
+use crate::levels::LevelSpec;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Answers considered before difficulty is adjusted.
+const WINDOW: usize = 5;
+/// At or above this accuracy, with quick answers, questions get harder.
+const RAISE_ACCURACY: f32 = 0.8;
+/// At or below this accuracy questions get easier.
+const LOWER_ACCURACY: f32 = 0.4;
+/// Answers slower than this (seconds, on average) never make things harder.
+const QUICK_RESPONSE: f64 = 3.0;
+/// Options are never moved further than this from what the level specifies.
+const MAX_OPTIONS_OFFSET: i32 = 4;
+/// Boss time limits are never scaled beyond this range.
+const MIN_TIME_SCALE: f64 = 0.5;
+const MAX_TIME_SCALE: f64 = 2.0;
+
+/// In-session difficulty adjustment driven by a rolling window of answers.
+///
+/// Sits on top of the curriculum: each level's `options` and `time_limit` are
+/// nudged by the offsets here, which carry across levels and retries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveDifficulty {
+    /// (correct, response time in seconds) for the most recent answers
+    recent: VecDeque<(bool, f64)>,
+    /// Added to the level's number of options
+    pub options_offset: i32,
+    /// Multiplies the level's time limit, above 1.0 gives more time
+    pub time_scale: f64,
+}
+
+impl Default for AdaptiveDifficulty {
+    fn default() -> Self {
+        Self {
+            recent: VecDeque::with_capacity(WINDOW),
+            options_offset: 0,
+            time_scale: 1.0,
+        }
+    }
+}
+
+impl AdaptiveDifficulty {
+    /// Adds an answer to the window, adjusting difficulty once it is full.
+    pub fn record(&mut self, correct: bool, response_time: f64) {
+        self.recent.push_back((correct, response_time));
+        if self.recent.len() < WINDOW {
+            return;
+        }
+
+        let count = self.recent.len();
+        let accuracy = self.recent.iter().filter(|(c, _)| *c).count() as f32 / count as f32;
+        let mean_time = self.recent.iter().map(|(_, t)| t).sum::<f64>() / count as f64;
+
+        if accuracy >= RAISE_ACCURACY && mean_time <= QUICK_RESPONSE {
+            self.options_offset = (self.options_offset + 1).min(MAX_OPTIONS_OFFSET);
+            self.time_scale = (self.time_scale * 0.8).max(MIN_TIME_SCALE);
+        } else if accuracy <= LOWER_ACCURACY {
+            self.options_offset = (self.options_offset - 1).max(-MAX_OPTIONS_OFFSET);
+            self.time_scale = (self.time_scale * 1.25).min(MAX_TIME_SCALE);
+        } else {
+            // Comfortable where they are, keep sliding the window
+            self.recent.pop_front();
+            return;
+        }
+        // Give the new difficulty a full window before judging it
+        self.recent.clear();
+    }
+
+    /// Number of options to show for `spec`, never more than the pool holds
+    /// and never fewer than two unless the level itself asks for fewer.
+    pub fn options(&self, spec: &LevelSpec, pool_len: usize) -> usize {
+        let floor = spec.options.min(2);
+        let adjusted = spec.options as i64 + i64::from(self.options_offset);
+        (adjusted.max(floor as i64) as usize).clamp(1, pool_len.max(1))
+    }
+
+    pub fn time_limit(&self, spec: &LevelSpec) -> Option<f64> {
+        spec.time_limit.map(|t| t * self.time_scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::Curriculum;
+
+    fn level(number: usize) -> LevelSpec {
+        Curriculum::default().levels[number - 1].clone()
+    }
+
+    fn answer(adaptive: &mut AdaptiveDifficulty, correct: bool, time: f64, times: usize) {
+        for _ in 0..times {
+            adaptive.record(correct, time);
+        }
+    }
+
+    #[test]
+    fn waits_for_a_full_window() {
+        let mut adaptive = AdaptiveDifficulty::default();
+        answer(&mut adaptive, true, 1.0, WINDOW - 1);
+        assert_eq!(adaptive.options_offset, 0);
+        assert_eq!(adaptive.time_scale, 1.0);
+        adaptive.record(true, 1.0);
+        assert_eq!(adaptive.options_offset, 1);
+    }
+
+    #[test]
+    fn quick_correct_answers_raise_difficulty() {
+        let mut adaptive = AdaptiveDifficulty::default();
+        answer(&mut adaptive, true, 1.0, WINDOW);
+        assert_eq!(adaptive.options_offset, 1);
+        assert!(adaptive.time_scale < 1.0);
+        assert_eq!(adaptive.options(&level(2), 26), 4);
+    }
+
+    #[test]
+    fn slow_correct_answers_hold_difficulty() {
+        let mut adaptive = AdaptiveDifficulty::default();
+        answer(&mut adaptive, true, QUICK_RESPONSE + 1.0, WINDOW * 3);
+        assert_eq!(adaptive.options_offset, 0);
+        assert_eq!(adaptive.time_scale, 1.0);
+    }
+
+    #[test]
+    fn low_accuracy_lowers_difficulty() {
+        let mut adaptive = AdaptiveDifficulty::default();
+        // Two right out of five is 40%
+        answer(&mut adaptive, true, 1.0, 2);
+        answer(&mut adaptive, false, 1.0, WINDOW - 2);
+        assert_eq!(adaptive.options_offset, -1);
+        assert!(adaptive.time_scale > 1.0);
+    }
+
+    #[test]
+    fn options_keep_a_floor_and_fit_the_pool() {
+        let mut adaptive = AdaptiveDifficulty::default();
+        answer(&mut adaptive, false, 1.0, WINDOW * 10);
+        assert_eq!(adaptive.options_offset, -MAX_OPTIONS_OFFSET);
+        assert_eq!(adaptive.options(&level(2), 26), 2);
+        assert_eq!(adaptive.options(&level(4), 26), 5);
+
+        answer(&mut adaptive, true, 1.0, WINDOW * 20);
+        assert_eq!(adaptive.options_offset, MAX_OPTIONS_OFFSET);
+        assert_eq!(adaptive.options(&level(3), 26), 9);
+        assert_eq!(adaptive.options(&level(3), 6), 6);
+    }
+
+    #[test]
+    fn time_scale_stays_in_bounds() {
+        let boss = level(4);
+        let mut adaptive = AdaptiveDifficulty::default();
+        answer(&mut adaptive, true, 1.0, WINDOW * 20);
+        assert_eq!(adaptive.time_scale, MIN_TIME_SCALE);
+        assert_eq!(adaptive.time_limit(&boss), Some(9.0 * MIN_TIME_SCALE));
+
+        answer(&mut adaptive, false, 1.0, WINDOW * 20);
+        assert_eq!(adaptive.time_scale, MAX_TIME_SCALE);
+        assert_eq!(adaptive.time_limit(&boss), Some(9.0 * MAX_TIME_SCALE));
+        assert_eq!(adaptive.time_limit(&level(1)), None);
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+const ENGLISH: &[&str] = &[
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+
+/// Welsh counts its digraphs as single letters, so "ll" comes after "l".
+const WELSH: &[&str] = &[
+    "a", "b", "c", "ch", "d", "dd", "e", "f", "ff", "g", "ng", "h", "i", "j", "l", "ll", "m", "n",
+    "o", "p", "ph", "r", "rh", "s", "t", "th", "u", "w", "y",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "ñ", "o", "p", "q", "r",
+    "s", "t", "u", "v", "w", "x", "y", "z",
+];
+
+/// The umlauts and ß are taught after z rather than interleaved.
+const GERMAN: &[&str] = &[
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z", "ä", "ö", "ü", "ß",
+];
+
+const GREEK: &[&str] = &[
+    "α", "β", "γ", "δ", "ε", "ζ", "η", "θ", "ι", "κ", "λ", "μ", "ν", "ξ", "ο", "π", "ρ", "σ", "τ",
+    "υ", "φ", "χ", "ψ", "ω",
+];
+
+const RUSSIAN: &[&str] = &[
+    "а", "б", "в", "г", "д", "е", "ё", "ж", "з", "и", "й", "к", "л", "м", "н", "о", "п", "р", "с",
+    "т", "у", "ф", "х", "ц", "ч", "ш", "щ", "ъ", "ы", "ь", "э", "ю", "я",
+];
+
+/// The alphabet the letter variants draw from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Alphabet {
+    #[default]
+    English,
+    /// Includes the digraphs ch, dd, ff, ng, ll, ph, rh and th as letters
+    Welsh,
+    Spanish,
+    German,
+    Greek,
+    /// Russian Cyrillic
+    Russian,
+}
+
+impl Alphabet {
+    pub const ALL: [Alphabet; 6] = [
+        Alphabet::English,
+        Alphabet::Welsh,
+        Alphabet::Spanish,
+        Alphabet::German,
+        Alphabet::Greek,
+        Alphabet::Russian,
+    ];
+
+    /// Name shown in settings, written in the alphabet's own language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Alphabet::English => "English",
+            Alphabet::Welsh => "Cymraeg",
+            Alphabet::Spanish => "Español",
+            Alphabet::German => "Deutsch",
+            Alphabet::Greek => "Ελληνικά",
+            Alphabet::Russian => "Русский",
+        }
+    }
+
+    /// Lowercase letters in alphabetical order.
+    pub fn letters(&self) -> &'static [&'static str] {
+        match self {
+            Alphabet::English => ENGLISH,
+            Alphabet::Welsh => WELSH,
+            Alphabet::Spanish => SPANISH,
+            Alphabet::German => GERMAN,
+            Alphabet::Greek => GREEK,
+            Alphabet::Russian => RUSSIAN,
+        }
+    }
+
+    pub fn lowercase(&self) -> Vec<String> {
+        self.letters().iter().map(|l| l.to_string()).collect()
+    }
+
+    pub fn uppercase(&self) -> Vec<String> {
+        self.letters().iter().map(|l| to_upper(l)).collect()
+    }
+
+    /// 1-based position of `letter` in this alphabet, in either case.
+    pub fn position(&self, letter: &str) -> Option<usize> {
+        let folded = fold(letter);
+        self.letters()
+            .iter()
+            .position(|l| *l == folded)
+            .map(|i| i + 1)
+    }
+}
+
+/// Lowercases a letter for comparison. Unicode's default mapping is right for
+/// every alphabet we ship, including capital ẞ to ß.
+pub fn fold(letter: &str) -> String {
+    letter.to_lowercase()
+}
+
+/// The capital form of a letter as it is printed on a letter card: ß becomes
+/// ẞ rather than "SS", and digraphs take a capital first letter only ("Ll").
+pub fn to_upper(letter: &str) -> String {
+    let mut chars = letter.chars();
+    match chars.next() {
+        Some('ß') => format!("ẞ{}", chars.as_str()),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether two letters are the same ignoring case.
+pub fn same_letter(a: &str, b: &str) -> bool {
+    fold(a) == fold(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capitals_match_letter_cards() {
+        assert_eq!(to_upper("a"), "A");
+        assert_eq!(to_upper("ß"), "ẞ");
+        assert_eq!(to_upper("ll"), "Ll");
+        assert_eq!(to_upper("ñ"), "Ñ");
+        assert_eq!(to_upper("σ"), "Σ");
+        assert_eq!(to_upper("ё"), "Ё");
+    }
+
+    #[test]
+    fn folding_undoes_capitals() {
+        for alphabet in Alphabet::ALL {
+            for letter in alphabet.letters() {
+                assert_eq!(fold(&to_upper(letter)), *letter, "{:?}", alphabet);
+            }
+        }
+        assert!(same_letter("Ll", "ll"));
+        assert!(same_letter("ẞ", "ß"));
+        assert!(!same_letter("l", "ll"));
+    }
+
+    #[test]
+    fn welsh_digraphs_are_single_letters() {
+        let welsh = Alphabet::Welsh;
+        assert_eq!(welsh.position("c"), Some(3));
+        assert_eq!(welsh.position("ch"), Some(4));
+        assert_eq!(welsh.position("Ll"), Some(16));
+        assert_eq!(welsh.position("y"), Some(29));
+        assert_eq!(welsh.position("k"), None);
+    }
+
+    #[test]
+    fn positions_follow_each_alphabet() {
+        assert_eq!(Alphabet::English.position("Z"), Some(26));
+        assert_eq!(Alphabet::Spanish.position("Ñ"), Some(15));
+        assert_eq!(Alphabet::German.position("ẞ"), Some(30));
+        assert_eq!(Alphabet::Greek.position("Ω"), Some(24));
+        assert_eq!(Alphabet::Russian.position("Ё"), Some(7));
+        assert_eq!(Alphabet::Russian.position("я"), Some(33));
+        assert_eq!(Alphabet::Russian.position("a"), None);
+    }
+}
@@ -0,0 +1,274 @@
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Tokens children commonly mix up by shape. Groups only need to overlap
+/// the pool in play; anything missing from it is ignored.
+const VISUAL_GROUPS: &[&[&str]] = &[
+    &["b", "d", "p", "q"],
+    &["m", "w", "n", "u"],
+    &["E", "F"],
+    &["O", "Q", "C", "G"],
+    &["P", "R", "B"],
+    &["M", "W", "N"],
+    &["I", "L", "T"],
+    &["U", "V"],
+    &["6", "9"],
+    &["1", "7"],
+    &["3", "8"],
+    &["2", "5"],
+    &["0", "8"],
+    &["12", "21"],
+    &["16", "19"],
+    // Sight words that are easy to read backwards or mistake at a glance
+    &["was", "saw"],
+    &["on", "no"],
+    &["of", "for"],
+    &["she", "he", "the"],
+    &["were", "where", "there"],
+];
+
+/// Tokens whose names sound alike when spoken.
+const PHONETIC_GROUPS: &[&[&str]] = &[
+    &["B", "D", "P", "T", "V", "G", "C", "E", "Z"],
+    &["M", "N"],
+    &["F", "S", "X"],
+    &["A", "J", "K"],
+    &["Q", "U", "W"],
+    &["I", "Y"],
+    &["13", "14", "15", "16", "17", "18", "19"],
+];
+
+/// Letter sounds that are easy to confuse when heard. Phonics tokens are
+/// lowercase sounds, not letter names, so these are matched exactly.
+const PHONEME_GROUPS: &[&[&str]] = &[
+    &["th", "f", "v"],
+    &["ch", "sh", "j"],
+    &["m", "n", "ng"],
+    &["c", "g"],
+    &["s", "z"],
+    &["b", "p"],
+    &["d", "t"],
+];
+
+/// Picks the wrong answers shown alongside the target.
+pub trait DistractorStrategy {
+    /// Returns up to `count` distinct tokens from `pool`, none equal to `target`.
+    fn distractors(
+        &self,
+        target: &str,
+        pool: &[String],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String>;
+}
+
+/// Which distractor strategy a level uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DistractorKind {
+    #[default]
+    Random,
+    /// Look-alikes such as b/d/p/q, m/w and 6/9
+    Visual,
+    /// Sound-alikes such as B/D/P/T/V and M/N
+    Phonetic,
+    /// Sound-alike letter sounds such as th/f/v and ch/sh/j, for phonics
+    Phonemes,
+    /// Numbers closest to the target, the off-by-one slips made when counting on
+    Nearby,
+}
+
+impl DistractorKind {
+    pub fn strategy(&self) -> &'static dyn DistractorStrategy {
+        match self {
+            DistractorKind::Random => &RandomDistractors,
+            DistractorKind::Visual => &SimilarDistractors {
+                groups: VISUAL_GROUPS,
+                ignore_case: false,
+            },
+            DistractorKind::Phonetic => &SimilarDistractors {
+                groups: PHONETIC_GROUPS,
+                ignore_case: true,
+            },
+            DistractorKind::Phonemes => &SimilarDistractors {
+                groups: PHONEME_GROUPS,
+                ignore_case: false,
+            },
+            DistractorKind::Nearby => &NearbyNumbers,
+        }
+    }
+}
+
+/// Uniform picks from the rest of the pool.
+pub struct RandomDistractors;
+
+impl DistractorStrategy for RandomDistractors {
+    fn distractors(
+        &self,
+        target: &str,
+        pool: &[String],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        let mut chosen = Vec::with_capacity(count);
+        fill_randomly(target, pool, count, rng, &mut chosen);
+        chosen
+    }
+}
+
+/// Prefers characters sharing a group with the target, topping up at random
+/// when the pool doesn't hold enough of them.
+pub struct SimilarDistractors {
+    groups: &'static [&'static [&'static str]],
+    /// Letter names sound the same in either case, so groups are matched ignoring case
+    ignore_case: bool,
+}
+
+impl SimilarDistractors {
+    fn same(&self, a: &str, b: &str) -> bool {
+        if self.ignore_case {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    }
+
+    fn in_group(&self, group: &[&str], token: &str) -> bool {
+        group.iter().any(|member| self.same(member, token))
+    }
+}
+
+impl DistractorStrategy for SimilarDistractors {
+    fn distractors(
+        &self,
+        target: &str,
+        pool: &[String],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        let mut similar: Vec<String> = pool
+            .iter()
+            .filter(|t| {
+                !self.same(t, target)
+                    && self
+                        .groups
+                        .iter()
+                        .any(|g| self.in_group(g, target) && self.in_group(g, t))
+            })
+            .cloned()
+            .collect();
+        similar.shuffle(rng);
+        similar.truncate(count);
+        fill_randomly(target, pool, count, rng, &mut similar);
+        similar
+    }
+}
+
+/// Numbers closest in value to a numeric target, ties broken at random.
+/// Non-numeric pools fall back to random picks.
+pub struct NearbyNumbers;
+
+impl DistractorStrategy for NearbyNumbers {
+    fn distractors(
+        &self,
+        target: &str,
+        pool: &[String],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        let mut chosen = Vec::with_capacity(count);
+        if let Ok(target_value) = target.parse::<i64>() {
+            let mut numbers: Vec<(i64, &String)> = pool
+                .iter()
+                .filter(|t| *t != target)
+                .filter_map(|t| t.parse::<i64>().ok().map(|n| (n, t)))
+                .collect();
+            numbers.shuffle(rng);
+            numbers.sort_by_key(|(n, _)| (n - target_value).abs());
+            chosen.extend(numbers.into_iter().take(count).map(|(_, t)| t.clone()));
+        }
+        fill_randomly(target, pool, count, rng, &mut chosen);
+        chosen
+    }
+}
+
+fn fill_randomly(
+    target: &str,
+    pool: &[String],
+    count: usize,
+    rng: &mut dyn RngCore,
+    chosen: &mut Vec<String>,
+) {
+    let mut rest: Vec<String> = pool
+        .iter()
+        .filter(|t| *t != target && !chosen.contains(t))
+        .cloned()
+        .collect();
+    rest.shuffle(rng);
+    chosen.extend(rest.into_iter().take(count.saturating_sub(chosen.len())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::phonics;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Checks `kind` offers every available member of `group` before topping
+    /// up, and never offers the target or the same token twice.
+    fn offers_group_first(kind: DistractorKind, target: &str, pool: &[String], group: &[&str]) {
+        let count = group.len() + 2;
+        for seed in 0..20 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let chosen = kind.strategy().distractors(target, pool, count, &mut rng);
+            assert_eq!(chosen.len(), count);
+            assert!(!chosen.iter().any(|t| t == target), "{:?}", chosen);
+            for (i, token) in chosen.iter().enumerate() {
+                assert!(!chosen[..i].contains(token), "{:?}", chosen);
+            }
+            let (similar, rest) = chosen.split_at(group.len());
+            assert!(
+                similar.iter().all(|t| group.contains(&t.as_str())),
+                "{:?}",
+                chosen
+            );
+            assert!(rest.iter().all(|t| pool.contains(t)), "{:?}", chosen);
+        }
+    }
+
+    #[test]
+    fn visual_offers_look_alikes_first() {
+        let pool = Alphabet::English.lowercase();
+        offers_group_first(DistractorKind::Visual, "b", &pool, &["d", "p", "q"]);
+    }
+
+    #[test]
+    fn phonetic_offers_sound_alikes_in_either_case() {
+        let upper = Alphabet::English.uppercase();
+        offers_group_first(DistractorKind::Phonetic, "M", &upper, &["N"]);
+        let lower = Alphabet::English.lowercase();
+        offers_group_first(DistractorKind::Phonetic, "m", &lower, &["n"]);
+    }
+
+    #[test]
+    fn phonemes_offer_sound_alikes_by_exact_grapheme() {
+        let pool: Vec<String> = phonics::PHONEMES
+            .iter()
+            .map(|p| p.grapheme.to_string())
+            .collect();
+        offers_group_first(DistractorKind::Phonemes, "th", &pool, &["f", "v"]);
+        offers_group_first(DistractorKind::Phonemes, "c", &pool, &["g"]);
+    }
+
+    #[test]
+    fn small_pools_give_what_they_have() {
+        let pool: Vec<String> = ["b", "d", "x"].iter().map(|t| t.to_string()).collect();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let chosen = DistractorKind::Visual
+            .strategy()
+            .distractors("b", &pool, 5, &mut rng);
+        assert_eq!(chosen, ["d", "x"]);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::action::Action;
+use crate::state::GameStatus;
+use thiserror::Error;
+
+/// Reasons the engine can refuse a request.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EngineError {
+    #[error("{action:?} is not allowed while in {status:?}")]
+    InvalidTransition {
+        action: Box<Action>,
+        status: Box<GameStatus>,
+    },
+
+    #[error("no active session")]
+    NoActiveSession,
+
+    #[error("option {index} is out of range ({len} options available)")]
+    OptionOutOfRange { index: usize, len: usize },
+
+    #[error("invalid settings: {0}")]
+    InvalidSettings(String),
+}
@@ -1,12 +1,48 @@
-use crate::settings::GameSettings;
-use crate::state::{GameStatus, GameVariant, SessionState, SoundEvent};
+use crate::action::Action;
+use crate::adaptive::AdaptiveDifficulty;
+use crate::alphabet;
+use crate::distractors::DistractorKind;
+use crate::error::EngineError;
+use crate::i18n::Message;
+use crate::levels::Curriculum;
+use crate::maths;
+use crate::phonics;
+use crate::profile::Profile;
+use crate::scheduler::Leitner;
+use crate::sequence;
+use crate::settings::{GameSettings, HintStyle, QuestionSelection, RetryPolicy};
+use crate::state::{
+    CountObject, EngineEvent, GameStatus, GameVariant, QuestionShape, SessionState, SoundEvent,
+};
+use crate::words::WordList;
 use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Timed questions emit a `TimerWarning` for each of their final seconds.
+const TIMER_WARNING_SECONDS: f64 = 5.0;
+
+/// Minimum gap between prompt replays, and between the first utterance and a
+/// replay, so a held or mashed key doesn't stack up recordings.
+const REPLAY_COOLDOWN_SECONDS: f64 = 1.5;
 
 pub struct GameEngine {
-    pub status: GameStatus,
-    pub session: Option<SessionState>,
-    pub last_sound: SoundEvent,
-    pub settings: GameSettings,
+    status: GameStatus,
+    session: Option<SessionState>,
+    events: Vec<EngineEvent>,
+    /// Settings in effect: the active profile's override, or the device-wide defaults
+    settings: GameSettings,
+    default_settings: GameSettings,
+    profile: Option<Profile>,
+    /// Set when the profile changed and a `ProfileUpdated` event is owed
+    profile_dirty: bool,
+    /// Fixed seed for new sessions, None to pick a fresh random seed each time
+    seed: Option<u32>,
+    /// ChaCha is used (rather than StdRng) because its output is stable across
+    /// platforms and releases, so a seed replays the same questions everywhere.
+    rng: ChaCha8Rng,
+    /// Words for `GameVariant::Words`
+    words: WordList,
 }
 
 impl Default for GameEngine {
@@ -17,233 +53,1085 @@ impl Default for GameEngine {
 
 impl GameEngine {
     pub fn new() -> Self {
-        Self {
-            status: GameStatus::Menu,
-            session: None,
-            last_sound: SoundEvent::None,
-            settings: GameSettings::default(),
-        }
+        Self::new_with_settings(GameSettings::default())
     }
 
     pub fn new_with_settings(settings: GameSettings) -> Self {
         Self {
-            status: GameStatus::Menu,
+            status: GameStatus::ProfileSelect,
             session: None,
-            last_sound: SoundEvent::None,
+            events: Vec::new(),
+            default_settings: settings.clone(),
             settings,
+            profile: None,
+            profile_dirty: false,
+            seed: None,
+            rng: ChaCha8Rng::seed_from_u64(0),
+            words: WordList::default(),
+        }
+    }
+
+    /// Builder-style variant of `set_seed` so every session replays the same questions.
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.set_seed(Some(seed));
+        self
+    }
+
+    /// Sets the seed used for subsequent sessions. None picks a random seed per session.
+    pub fn set_seed(&mut self, seed: Option<u32>) {
+        self.seed = seed;
+    }
+
+    /// Builder-style variant of `set_word_list`.
+    pub fn with_word_list(mut self, words: WordList) -> Self {
+        self.set_word_list(words);
+        self
+    }
+
+    /// Replaces the built-in word list used by the word-building variant.
+    pub fn set_word_list(&mut self, words: WordList) {
+        self.words = words;
+    }
+
+    pub fn seed(&self) -> Option<u32> {
+        self.seed
+    }
+
+    pub fn status(&self) -> &GameStatus {
+        &self.status
+    }
+
+    pub fn session(&self) -> Option<&SessionState> {
+        self.session.as_ref()
+    }
+
+    pub fn settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    /// `message` in the language of the settings in effect.
+    pub fn render(&self, message: &Message) -> String {
+        self.settings.language.catalogue().render(message)
+    }
+
+    /// Single entry point for frontends. The action is checked against the current
+    /// status and rejected with `EngineError::InvalidTransition` if it isn't valid there.
+    pub fn dispatch(&mut self, action: Action) -> Result<(), EngineError> {
+        if !self.accepts(&action) {
+            return Err(EngineError::InvalidTransition {
+                action: Box::new(action),
+                status: Box::new(self.status.clone()),
+            });
+        }
+
+        let mark = self.events.len();
+        let status_before = self.status.clone();
+        let scores_before = self.scores();
+
+        let result = self.apply(action);
+
+        // Announce state and score changes ahead of the cues they caused
+        let scores = self.scores();
+        if scores != scores_before {
+            if let Some((score, total_score)) = scores {
+                self.events
+                    .insert(mark, EngineEvent::ScoreChanged { score, total_score });
+            }
         }
+        if self.status != status_before {
+            if let (&GameStatus::SessionComplete { score }, Some(profile)) =
+                (&self.status, &mut self.profile)
+            {
+                profile.stats.sessions_completed += 1;
+                profile.stats.best_score = profile.stats.best_score.max(score);
+                self.profile_dirty = true;
+            }
+            self.events
+                .insert(mark, EngineEvent::StatusChanged(self.status.clone()));
+        }
+        if std::mem::take(&mut self.profile_dirty) {
+            if let Some(profile) = &self.profile {
+                self.events
+                    .push(EngineEvent::ProfileUpdated(Box::new(profile.clone())));
+            }
+        }
+        result
     }
 
-    pub fn start_game(&mut self, variant: GameVariant) {
+    fn apply(&mut self, action: Action) -> Result<(), EngineError> {
+        match action {
+            Action::StartGame(variant) => self.start_game(variant),
+            Action::Submit(input) => self.submit_answer(input),
+            Action::SubmitSelection => self.submit_current_selection(),
+            Action::MoveSelection(delta) => self.move_selection(delta),
+            Action::RequestHint => self.request_hint(),
+            Action::ReplayPrompt => self.replay_prompt(),
+            Action::Tick(dt_seconds) => self.tick(dt_seconds),
+            Action::Continue => match self.status {
+                GameStatus::Feedback { .. } => self.next_level(),
+                _ => self.advance_to_next_level_or_retry(),
+            },
+            Action::Pause => {
+                self.status = GameStatus::Paused {
+                    resume: Box::new(self.status.clone()),
+                };
+                Ok(())
+            }
+            Action::Resume => {
+                if let GameStatus::Paused { resume } = &self.status {
+                    self.status = (**resume).clone();
+                }
+                Ok(())
+            }
+            Action::Quit => {
+                self.return_to_menu();
+                Ok(())
+            }
+            Action::OpenSettings => {
+                self.status = GameStatus::Settings { message: None };
+                Ok(())
+            }
+            Action::UpdateSettings(settings) => {
+                settings.validate()?;
+                // Changes made while a child is playing only apply to them
+                match &mut self.profile {
+                    Some(profile) => {
+                        profile.settings = Some(settings.clone());
+                        self.profile_dirty = true;
+                    }
+                    None => self.default_settings = settings.clone(),
+                }
+                self.settings = settings;
+                if let GameStatus::Settings { message } = &mut self.status {
+                    *message = None;
+                }
+                Ok(())
+            }
+            Action::UseDefaultSettings => {
+                if let Some(profile) = &mut self.profile {
+                    profile.settings = None;
+                    self.profile_dirty = true;
+                }
+                self.settings = self.default_settings.clone();
+                if let GameStatus::Settings { message } = &mut self.status {
+                    *message = None;
+                }
+                Ok(())
+            }
+            Action::ShowSettingsMessage(text) => {
+                self.status = GameStatus::Settings {
+                    message: Some(text),
+                };
+                Ok(())
+            }
+            Action::OpenAbout => {
+                self.go_to_about();
+                Ok(())
+            }
+            Action::OpenProgress => {
+                self.status = GameStatus::Progress;
+                Ok(())
+            }
+            Action::SelectProfile(profile) => self.select_profile(profile),
+            Action::SwitchProfile => {
+                self.profile = None;
+                self.settings = self.default_settings.clone();
+                self.status = GameStatus::ProfileSelect;
+                Ok(())
+            }
+        }
+    }
+
+    fn scores(&self) -> Option<(u32, u32)> {
+        self.session.as_ref().map(|s| (s.score, s.total_score))
+    }
+
+    /// Whether `action` is a valid transition from the current status.
+    pub fn accepts(&self, action: &Action) -> bool {
+        match (&self.status, action) {
+            // Timers tick continuously; outside of Playing this is a no-op
+            (_, Action::Tick(_)) => true,
+            (GameStatus::Menu | GameStatus::ProfileSelect, Action::Quit) => false,
+            (_, Action::Quit) => true,
+            (
+                GameStatus::Menu,
+                Action::StartGame(_)
+                | Action::OpenSettings
+                | Action::OpenAbout
+                | Action::UpdateSettings(_)
+                | Action::SwitchProfile,
+            ) => true,
+            (GameStatus::Menu, Action::OpenProgress) => self.profile.is_some(),
+            (GameStatus::ProfileSelect, Action::SelectProfile(_)) => true,
+            (
+                GameStatus::Settings { .. },
+                Action::UpdateSettings(_)
+                | Action::UseDefaultSettings
+                | Action::ShowSettingsMessage(_),
+            ) => true,
+            (
+                GameStatus::Playing,
+                Action::Submit(_)
+                | Action::SubmitSelection
+                | Action::MoveSelection(_)
+                | Action::RequestHint
+                | Action::ReplayPrompt,
+            ) => true,
+            (GameStatus::Feedback { .. } | GameStatus::LevelComplete { .. }, Action::Continue) => {
+                true
+            }
+            (GameStatus::Playing | GameStatus::Feedback { .. }, Action::Pause) => true,
+            (GameStatus::Paused { .. }, Action::Resume) => true,
+            _ => false,
+        }
+    }
+
+    fn select_profile(&mut self, profile: Profile) -> Result<(), EngineError> {
+        let settings = profile
+            .settings
+            .clone()
+            .unwrap_or_else(|| self.default_settings.clone());
+        settings.validate()?;
+        self.settings = settings;
+        self.profile = Some(profile);
+        self.status = GameStatus::Menu;
+        Ok(())
+    }
+
+    fn start_game(&mut self, variant: GameVariant) -> Result<(), EngineError> {
+        self.settings.validate()?;
+
+        let pool = self.settings.pool_for(&variant)?;
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        self.rng = ChaCha8Rng::seed_from_u64(u64::from(seed));
+
+        let curriculum = &self.settings.curriculum;
         let mut session = SessionState {
             variant,
+            seed,
             current_level: self.settings.start_level,
+            total_levels: curriculum.len(),
+            is_boss_level: false,
             current_question_index: 0,
-            total_questions: 5, // Default for non-boss levels
+            total_questions: 0,
             score: 0,
+            credit: 0.0,
             total_score: 0,
-            target: ' ',
+            target: String::new(),
+            prompt: String::new(),
             options: vec![],
+            question: QuestionShape::Choose,
             selected_index: 0,
             level_time_limit: None,
             level_elapsed_time: 0.0,
+            question_elapsed_time: 0.0,
+            attempt: 0,
+            hints_used: 0,
+            highlighted: None,
+            prompt_replays: 0,
+            last_replay_at: None,
+            adaptive: self
+                .settings
+                .adaptive_difficulty
+                .then(AdaptiveDifficulty::default),
         };
 
-        Self::setup_level_properties(&mut session); // Call here to setup level 1 total_questions
-        Self::generate_level_question(&mut session);
+        Self::setup_level_properties(curriculum, &mut session);
+        let schedule = Self::schedule(&self.settings, &self.profile);
+        Self::generate_level_question(
+            curriculum,
+            schedule,
+            &pool,
+            &self.words,
+            &mut self.rng,
+            &mut session,
+        );
+        self.events.push(EngineEvent::Sound(SoundEvent::GameStart));
+        self.events
+            .extend(session.prompt_sounds().into_iter().map(EngineEvent::Sound));
         self.session = Some(session);
         self.status = GameStatus::Playing;
 
-        if let Some(s) = &self.session {
-            self.last_sound = SoundEvent::SayPrompt(s.target);
+        if let Some(profile) = &mut self.profile {
+            profile.stats.sessions_started += 1;
+            self.profile_dirty = true;
         }
+        Ok(())
     }
 
-    fn setup_level_properties(session: &mut SessionState) {
-        match session.current_level {
-            1..=3 => session.total_questions = 5,
-            _ => session.total_questions = 1,
+    fn setup_level_properties(curriculum: &Curriculum, session: &mut SessionState) {
+        let spec = curriculum.level(session.current_level);
+        session.total_questions = spec.questions;
+        session.is_boss_level = spec.boss;
+    }
+
+    /// The Leitner history to draw targets from, if spaced repetition is in use.
+    fn schedule<'a>(settings: &GameSettings, profile: &'a Option<Profile>) -> Option<&'a Leitner> {
+        match settings.question_selection {
+            QuestionSelection::SpacedRepetition => profile.as_ref().map(|p| &p.leitner),
+            QuestionSelection::Uniform => None,
         }
     }
 
-    fn generate_level_question(session: &mut SessionState) {
-        let mut rng = rand::thread_rng();
+    fn generate_level_question(
+        curriculum: &Curriculum,
+        schedule: Option<&Leitner>,
+        pool: &[String],
+        words: &WordList,
+        rng: &mut ChaCha8Rng,
+        session: &mut SessionState,
+    ) {
+        let spec = curriculum.level(session.current_level);
 
-        let (num_options, time_limit) = match session.current_level {
-            1 => (2, None),
-            2 => (3, None),
-            3 => (5, None),
-            _ => (9, Some(9.0)),
-        };
-
-        session.level_time_limit = time_limit;
         session.level_elapsed_time = 0.0;
 
-        let pool = session.variant.char_pool();
-        let target = *pool.choose(&mut rng).unwrap();
+        let num_options = match &session.adaptive {
+            Some(adaptive) => {
+                session.level_time_limit = adaptive.time_limit(spec);
+                adaptive.options(spec, pool.len())
+            }
+            None => {
+                session.level_time_limit = spec.time_limit;
+                spec.options.clamp(1, pool.len())
+            }
+        };
+        let spelling = session.variant == GameVariant::Words;
+        let targets = if spelling { words.words() } else { pool };
+        let target = schedule
+            .and_then(|leitner| leitner.choose(targets, rng))
+            .unwrap_or_else(|| targets.choose(rng).unwrap().clone());
 
-        let mut options = vec![target];
-        while options.len() < num_options {
-            let choice = *pool.choose(&mut rng).unwrap();
-            if !options.contains(&choice) {
-                options.push(choice);
+        let mut options = if spelling {
+            // Every letter of the word is offered, topped up with distractors
+            let mut letters: Vec<String> = Vec::new();
+            for letter in target.chars().map(String::from) {
+                if !letters.contains(&letter) {
+                    letters.push(letter);
+                }
             }
-        }
-        options.shuffle(&mut rng);
+            let others: Vec<String> = pool
+                .iter()
+                .filter(|l| !letters.contains(l))
+                .cloned()
+                .collect();
+            let extra = num_options.saturating_sub(letters.len());
+            let mut options =
+                spec.distractors
+                    .strategy()
+                    .distractors(&letters[0], &others, extra, rng);
+            options.extend(letters);
+            session.question = QuestionShape::Spell { built: Vec::new() };
+            options
+        } else {
+            // Sums and counting go wrong by one or two, whatever the level asks for,
+            // and phonics sounds are confused with other sounds, not letter names
+            let kind = match session.variant {
+                GameVariant::Maths | GameVariant::Counting => DistractorKind::Nearby,
+                GameVariant::Phonics => DistractorKind::Phonemes,
+                _ => spec.distractors,
+            };
+            let mut options = kind
+                .strategy()
+                .distractors(&target, pool, num_options - 1, rng);
+            options.push(target.clone());
+            session.question = match session.variant {
+                GameVariant::Counting => {
+                    let quantity = target.parse().unwrap_or(1);
+                    QuestionShape::Count {
+                        quantity,
+                        object: *CountObject::ALL.choose(rng).unwrap(),
+                        // Rows of five, like a tens frame
+                        columns: quantity.min(5),
+                    }
+                }
+                GameVariant::Sequence => QuestionShape::Sequence {
+                    items: sequence::run_with_gap(pool, &target, rng),
+                },
+                _ => QuestionShape::Choose,
+            };
+            options
+        };
+        options.shuffle(rng);
 
+        session.prompt = match (&session.question, &session.variant) {
+            (QuestionShape::Sequence { items }, _) => sequence::written(items),
+            (_, GameVariant::Maths) => maths::expression_for(target.parse().unwrap_or(0), rng),
+            (_, variant) => variant.prompt_for(&target),
+        };
         session.target = target;
         session.options = options;
         session.selected_index = 0;
+        session.question_elapsed_time = 0.0;
+        session.hints_used = 0;
+        session.highlighted = None;
+        session.prompt_replays = 0;
+        session.last_replay_at = None;
     }
 
-    pub fn next_level(&mut self) {
-        if let Some(session) = &mut self.session {
-            session.current_question_index += 1;
-
-            if session.current_question_index >= session.total_questions {
-                let threshold = (session.total_questions as f32 * 0.8).ceil() as u32;
-                let passed = session.score >= threshold;
-
-                if passed && session.current_level >= 4 {
-                    session.total_score += session.score;
-                    self.status = GameStatus::SessionComplete {
-                        score: session.total_score,
-                    };
-                    self.last_sound = SoundEvent::LevelComplete;
-                } else {
-                    self.status = GameStatus::LevelComplete {
-                        level: session.current_level,
-                        score: session.score,
-                        passed,
-                    };
-                    self.last_sound = if passed {
-                        SoundEvent::LevelComplete
-                    } else {
-                        SoundEvent::None
-                    };
-                }
+    fn next_level(&mut self) -> Result<(), EngineError> {
+        let curriculum = &self.settings.curriculum;
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        if session.attempt > 0 {
+            Self::retry_question(self.settings.retry_policy, session);
+            self.status = GameStatus::Playing;
+            self.events
+                .extend(session.prompt_sounds().into_iter().map(EngineEvent::Sound));
+            return Ok(());
+        }
+        session.current_question_index += 1;
+
+        if session.current_question_index >= session.total_questions {
+            let threshold = curriculum.level(session.current_level).required_score();
+            let passed = session.credit >= threshold as f32;
+
+            if passed && curriculum.is_final(session.current_level) {
+                session.total_score += session.score;
+                self.status = GameStatus::SessionComplete {
+                    score: session.total_score,
+                };
+                self.events
+                    .push(EngineEvent::Sound(SoundEvent::LevelComplete));
             } else {
-                Self::generate_level_question(session);
-                self.status = GameStatus::Playing;
-                self.last_sound = SoundEvent::SayPrompt(session.target);
+                self.status = GameStatus::LevelComplete {
+                    level: session.current_level,
+                    score: session.score,
+                    passed,
+                };
+                if passed {
+                    self.events
+                        .push(EngineEvent::Sound(SoundEvent::LevelComplete));
+                }
             }
+        } else {
+            let pool = self.settings.pool_for(&session.variant)?;
+            let schedule = Self::schedule(&self.settings, &self.profile);
+            Self::generate_level_question(
+                curriculum,
+                schedule,
+                &pool,
+                &self.words,
+                &mut self.rng,
+                session,
+            );
+            self.status = GameStatus::Playing;
+            self.events
+                .extend(session.prompt_sounds().into_iter().map(EngineEvent::Sound));
         }
+        Ok(())
     }
 
-    pub fn advance_to_next_level_or_retry(&mut self) {
-        if let GameStatus::LevelComplete { passed, level, .. } = self.status {
-            if let Some(session) = &mut self.session {
-                if passed {
-                    // Accumulate score before moving on or finishing
-                    session.total_score += session.score;
+    /// Asks the current question again after a wrong answer.
+    fn retry_question(policy: RetryPolicy, session: &mut SessionState) {
+        if let RetryPolicy::RetryWithFewerOptions { .. } = policy {
+            let mut rng = Self::removal_rng(session);
+            Self::remove_wrong_option(&mut rng, session);
+        }
+        if let QuestionShape::Spell { built } = &mut session.question {
+            built.clear();
+        }
+        session.highlighted = None;
+        session.last_replay_at = None;
+        session.selected_index = 0;
+        session.level_elapsed_time = 0.0;
+        session.question_elapsed_time = 0.0;
+    }
 
-                    if level >= 4 {
-                        // Boss level is 4. Passing level 4 means game over!
-                        self.status = GameStatus::SessionComplete {
-                            score: session.total_score,
-                        };
-                        self.last_sound = SoundEvent::LevelComplete;
-                        return;
+    /// Randomness for taking options off the question on screen. It is kept
+    /// apart from the question generator so hints and retries don't change which
+    /// questions come later in a seeded game.
+    fn removal_rng(session: &SessionState) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(u64::from(session.seed));
+        rng.set_stream(
+            (u64::from(session.current_level) << 40)
+                | (u64::from(session.current_question_index) << 16)
+                | session.options.len() as u64,
+        );
+        rng
+    }
+
+    /// Takes away one option that isn't part of the answer, always leaving at
+    /// least one wrong option to choose between. Returns false if none could go.
+    fn remove_wrong_option(rng: &mut ChaCha8Rng, session: &mut SessionState) -> bool {
+        // Letters of the word being spelled are needed, as is the target itself
+        let needed = |o: &String| match session.question {
+            QuestionShape::Spell { .. } => session.target.contains(o.as_str()),
+            _ => *o == session.target,
+        };
+        let spare: Vec<usize> = (0..session.options.len())
+            .filter(|&i| !needed(&session.options[i]))
+            .collect();
+        if spare.len() < 2 {
+            return false;
+        }
+        let index = *spare.choose(rng).unwrap();
+        session.options.remove(index);
+        session.selected_index = session.selected_index.min(session.options.len() - 1);
+        true
+    }
+
+    /// Gives the hint chosen in settings. Removing an option falls back to
+    /// replaying the prompt once there are too few left to take one away.
+    fn request_hint(&mut self) -> Result<(), EngineError> {
+        let style = self.settings.hint_style;
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        session.hints_used += 1;
+        if let Some(profile) = &mut self.profile {
+            profile.stats.hints_used += 1;
+            self.profile_dirty = true;
+        }
+
+        let shown = match style {
+            HintStyle::RemoveOption => {
+                let mut rng = Self::removal_rng(session);
+                Self::remove_wrong_option(&mut rng, session)
+            }
+            HintStyle::HighlightTarget => {
+                session.highlighted = match &session.question {
+                    QuestionShape::Spell { built } => {
+                        session.target.chars().nth(built.len()).map(String::from)
                     }
-                    session.current_level += 1;
-                }
-                // Reset for new level (or retry)
-                // Note: We do NOT reset total_score here, only current level score
-                session.current_question_index = 0;
-                session.score = 0;
-                session.level_elapsed_time = 0.0;
-                Self::setup_level_properties(session); // Re-evaluate total_questions
+                    _ => Some(session.target.clone()),
+                };
+                true
+            }
+            HintStyle::ReplayPrompt => false,
+        };
+        if !shown {
+            Self::say_prompt_again(session, &mut self.events);
+        }
+        Ok(())
+    }
+
+    fn replay_prompt(&mut self) -> Result<(), EngineError> {
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        Self::say_prompt_again(session, &mut self.events);
+        Ok(())
+    }
+
+    /// Says the prompt again unless it was said within `REPLAY_COOLDOWN_SECONDS`.
+    /// Both replays and spoken hints go through here, so mashing either key
+    /// can't stack up recordings.
+    fn say_prompt_again(session: &mut SessionState, events: &mut Vec<EngineEvent>) {
+        let now = session.question_elapsed_time;
+        let last = session.last_replay_at.unwrap_or(0.0);
+        if now - last < REPLAY_COOLDOWN_SECONDS {
+            return;
+        }
+        session.prompt_replays += 1;
+        session.last_replay_at = Some(now);
+        events.extend(session.prompt_sounds().into_iter().map(EngineEvent::Sound));
+    }
+
+    fn advance_to_next_level_or_retry(&mut self) -> Result<(), EngineError> {
+        let curriculum = &self.settings.curriculum;
+        let GameStatus::LevelComplete { passed, level, .. } = self.status else {
+            return Err(EngineError::InvalidTransition {
+                action: Box::new(Action::Continue),
+                status: Box::new(self.status.clone()),
+            });
+        };
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
 
-                Self::generate_level_question(session);
-                self.status = GameStatus::Playing;
-                self.last_sound = SoundEvent::SayPrompt(session.target);
+        if passed {
+            // Accumulate score before moving on or finishing
+            session.total_score += session.score;
+
+            if curriculum.is_final(level) {
+                // Passing the final level means game over!
+                self.status = GameStatus::SessionComplete {
+                    score: session.total_score,
+                };
+                self.events
+                    .push(EngineEvent::Sound(SoundEvent::LevelComplete));
+                return Ok(());
             }
+            session.current_level += 1;
         }
+        // Reset for new level (or retry)
+        // Note: We do NOT reset total_score here, only current level score
+        session.current_question_index = 0;
+        session.score = 0;
+        session.credit = 0.0;
+        session.level_elapsed_time = 0.0;
+        Self::setup_level_properties(curriculum, session); // Re-evaluate total_questions
+
+        let pool = self.settings.pool_for(&session.variant)?;
+        let schedule = Self::schedule(&self.settings, &self.profile);
+        Self::generate_level_question(
+            curriculum,
+            schedule,
+            &pool,
+            &self.words,
+            &mut self.rng,
+            session,
+        );
+        self.status = GameStatus::Playing;
+        self.events
+            .extend(session.prompt_sounds().into_iter().map(EngineEvent::Sound));
+        Ok(())
     }
 
-    pub fn tick(&mut self, dt_seconds: f64) {
+    fn tick(&mut self, dt_seconds: f64) -> Result<(), EngineError> {
         if let GameStatus::Playing = self.status {
             if let Some(session) = &mut self.session {
+                session.question_elapsed_time += dt_seconds;
                 if let Some(limit) = session.level_time_limit {
+                    let left_before = (limit - session.level_elapsed_time).ceil();
                     session.level_elapsed_time += dt_seconds;
+                    let left = (limit - session.level_elapsed_time).ceil();
+                    if left < left_before && left > 0.0 && left <= TIMER_WARNING_SECONDS {
+                        self.events.push(EngineEvent::TimerWarning {
+                            seconds_left: left as u32,
+                        });
+                    }
                     if session.level_elapsed_time >= limit {
                         // Timeout!
+                        if let Some(profile) = &mut self.profile {
+                            profile.mastery.record(
+                                &session.target,
+                                None,
+                                session.question_elapsed_time,
+                            );
+                            // Counted like a wrong first answer in `finish_question`
+                            if session.attempt == 0 {
+                                profile.stats.questions_answered += 1;
+                                profile.leitner.record(&session.target, false);
+                            }
+                            self.profile_dirty = true;
+                        }
+                        // Time's up moves on whatever the retry policy
+                        session.attempt = 0;
+                        if let Some(adaptive) = &mut session.adaptive {
+                            adaptive.record(false, session.question_elapsed_time);
+                        }
                         self.status = GameStatus::Feedback {
                             success: false,
-                            message: "Time's Up!".to_string(),
+                            message: Message::new("times_up"),
                         };
-                        self.last_sound = SoundEvent::PlayFailure;
+                        self.events
+                            .push(EngineEvent::Sound(SoundEvent::PlayFailure));
                     }
                 }
             }
         }
+        Ok(())
     }
 
-    pub fn move_selection(&mut self, delta: i32) {
-        if let Some(session) = &mut self.session {
-            let len = session.options.len() as i32;
-            if len == 0 {
-                return;
-            }
+    fn move_selection(&mut self, delta: i32) -> Result<(), EngineError> {
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        let len = session.options.len() as i32;
+        if len == 0 {
+            return Ok(());
+        }
+
+        let new_index = (session.selected_index as i32 + delta).rem_euclid(len);
+        session.selected_index = new_index as usize;
+        Ok(())
+    }
+
+    fn submit_current_selection(&mut self) -> Result<(), EngineError> {
+        let session = self.session.as_ref().ok_or(EngineError::NoActiveSession)?;
+        let choice = session
+            .options
+            .get(session.selected_index)
+            .ok_or(EngineError::OptionOutOfRange {
+                index: session.selected_index,
+                len: session.options.len(),
+            })?
+            .clone();
+        self.submit_answer(choice)
+    }
 
-            let new_index = (session.selected_index as i32 + delta).rem_euclid(len);
-            session.selected_index = new_index as usize;
+    fn submit_answer(&mut self, input: String) -> Result<(), EngineError> {
+        let alphabet = self.settings.alphabet;
+        let retry = self.retry_left();
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        if let QuestionShape::Spell { .. } = session.question {
+            return self.submit_letter(input);
         }
+        let is_correct = session.variant.matches(&input, &session.target);
+        // Record the option the key matched so 'b' and 'B' count as the same mix-up
+        // (unless case is what's being tested)
+        let chosen = session
+            .options
+            .iter()
+            .find(|o| session.variant.matches(&input, o))
+            .unwrap_or(&input)
+            .clone();
+
+        let message = if !is_correct && retry {
+            Message::new("wrong").arg("input", &input)
+        } else if !is_correct {
+            Message::new("wrong_moving_on")
+                .arg("input", &input)
+                .arg("target", &session.target)
+        } else if session.variant == GameVariant::Phonics {
+            // The sound that was played, which isn't always the grapheme ("c" says /k/)
+            let sound =
+                phonics::by_grapheme(&session.target).map_or(session.target.as_str(), |p| p.id);
+            Message::new("phonics")
+                .arg("input", &input)
+                .arg("sound", sound)
+        } else if let QuestionShape::Count {
+            quantity, object, ..
+        } = &session.question
+        {
+            Message::new(&format!("counted_{}", object.name())).arg("count", quantity)
+        } else if session.variant == GameVariant::Sequence {
+            Message::new("sequence").arg("target", &session.target)
+        } else if session.variant == GameVariant::Maths {
+            Message::new("sum")
+                .arg("expression", &session.prompt)
+                .arg("answer", &session.target)
+        } else if session.variant == GameVariant::SightWords {
+            Message::new("sight_word").arg("word", &session.target)
+        } else if let Ok(num) = input.parse::<u32>() {
+            let key = if num.is_multiple_of(2) {
+                "even_number"
+            } else {
+                "odd_number"
+            };
+            Message::new(key).arg("input", &input)
+        } else if let Some(pos) = alphabet.position(&input) {
+            let shown = if session.variant.case_sensitive() {
+                input.clone()
+            } else {
+                alphabet::fold(&input)
+            };
+            Message::new("letter")
+                .arg("letter", shown)
+                .arg("position", pos)
+        } else {
+            Message::new("great_job")
+        };
+
+        let credit = if is_correct { 1.0 } else { 0.0 };
+        self.finish_question(&chosen, credit, message, retry);
+        Ok(())
     }
 
-    pub fn submit_current_selection(&mut self) {
-        if let Some(session) = &self.session {
-            if let Some(&selected_char) = session.options.get(session.selected_index) {
-                // We need to clone the char to avoid borrowing issues since submit_answer uses &mut self
-                let choice = selected_char;
-                self.submit_answer(choice);
-            }
+    /// Adds a letter to the word being spelled, scoring the word once every
+    /// letter has been placed.
+    fn submit_letter(&mut self, input: String) -> Result<(), EngineError> {
+        let retry = self.retry_left();
+        let session = self.session.as_mut().ok_or(EngineError::NoActiveSession)?;
+        let letter = session
+            .options
+            .iter()
+            .find(|o| o.eq_ignore_ascii_case(&input))
+            .unwrap_or(&input)
+            .clone();
+        let QuestionShape::Spell { built } = &mut session.question else {
+            return Ok(());
+        };
+        built.push(letter);
+        // A highlighted letter has been used up
+        session.highlighted = None;
+
+        let word: Vec<String> = session.target.chars().map(String::from).collect();
+        if built.len() < word.len() {
+            return Ok(());
         }
+
+        let right = built.iter().zip(&word).filter(|(b, w)| b == w).count();
+        let spelled = built.concat();
+        let message = if right == word.len() {
+            Message::new("spelled").arg("word", &session.target)
+        } else if retry {
+            Message::new("partly_spelled_retry")
+                .arg("right", right)
+                .arg("total", word.len())
+        } else {
+            Message::new("partly_spelled")
+                .arg("right", right)
+                .arg("total", word.len())
+                .arg("word", &session.target)
+        };
+        let credit = right as f32 / word.len() as f32;
+        self.finish_question(&spelled, credit, message, retry);
+        Ok(())
     }
 
-    pub fn submit_answer(&mut self, input: char) {
-        if let Some(session) = &mut self.session {
-            let is_correct = input.eq_ignore_ascii_case(&session.target);
+    /// Whether a wrong answer now would be followed by another go at the question.
+    fn retry_left(&self) -> bool {
+        self.session
+            .as_ref()
+            .is_some_and(|s| s.attempt < self.settings.retry_policy.retries())
+    }
 
-            if is_correct {
-                session.score += 1;
-                let message = if input.is_ascii_digit() {
-                    let num = input.to_digit(10).unwrap_or(0);
-                    let parity = if num.is_multiple_of(2) {
-                        "an even"
-                    } else {
-                        "an odd"
-                    };
-                    format!("You chose {}, {} number", input, parity)
-                } else if input.is_ascii_alphabetic() {
-                    let lower = input.to_ascii_lowercase();
-                    let pos = lower as u8 - b'a' + 1;
-                    format!("You chose {}, letter number {} in the alphabet", lower, pos)
-                } else {
-                    "Great Job!".to_string()
-                };
+    /// Scores the current question and shows feedback. `credit` is 1.0 for a
+    /// fully correct answer and a fraction for a partly-spelled word. With
+    /// `retry`, a wrong answer earns nothing yet and the question is asked again.
+    fn finish_question(&mut self, chosen: &str, credit: f32, message: Message, retry: bool) {
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        let is_correct = credit >= 1.0;
+        let first_try = session.attempt == 0;
 
-                self.status = GameStatus::Feedback {
-                    success: true,
-                    message,
-                };
-                self.last_sound = SoundEvent::PlaySuccess;
-            } else {
-                self.status = GameStatus::Feedback {
-                    success: false,
-                    message: format!("Oops! That was {}. Try again!", input),
-                };
-                self.last_sound = SoundEvent::PlayFailure;
+        if let Some(profile) = &mut self.profile {
+            if first_try {
+                profile.stats.questions_answered += 1;
+                profile.leitner.record(&session.target, is_correct);
+                if is_correct {
+                    profile.stats.correct_answers += 1;
+                }
+            } else if is_correct {
+                profile.stats.correct_after_retry += 1;
+            }
+            profile
+                .mastery
+                .record(&session.target, Some(chosen), session.question_elapsed_time);
+            self.profile_dirty = true;
+        }
+        if let Some(adaptive) = &mut session.adaptive {
+            adaptive.record(is_correct, session.question_elapsed_time);
+        }
+
+        if !is_correct && retry {
+            session.attempt += 1;
+        } else {
+            let penalty = session.hints_used as f32 * self.settings.hint_penalty;
+            let earned = (credit - penalty).max(0.0);
+            session.attempt = 0;
+            session.credit += earned;
+            if is_correct && earned >= 1.0 {
+                session.score += 1;
             }
         }
+        self.status = GameStatus::Feedback {
+            success: is_correct,
+            message,
+        };
+        let sound = if is_correct {
+            SoundEvent::PlaySuccess
+        } else {
+            SoundEvent::PlayFailure
+        };
+        self.events.push(EngineEvent::Sound(sound));
     }
 
-    // Consumes the last sound event (so it doesn't loop)
-    pub fn consume_sound(&mut self) -> SoundEvent {
-        std::mem::take(&mut self.last_sound)
+    /// Takes all events emitted since the last call, oldest first.
+    pub fn drain_events(&mut self) -> Vec<EngineEvent> {
+        std::mem::take(&mut self.events)
     }
 
-    pub fn go_to_about(&mut self) {
+    fn go_to_about(&mut self) {
         self.status = GameStatus::About;
     }
+
+    fn return_to_menu(&mut self) {
+        self.status = GameStatus::Menu;
+        self.session = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+
+    type Question = (String, Vec<String>);
+
+    fn start(settings: GameSettings, seed: u32, variant: GameVariant) -> GameEngine {
+        let mut engine = GameEngine::new_with_settings(settings).with_seed(seed);
+        engine
+            .dispatch(Action::SelectProfile(Profile::new("Sam", "🦊", 0)))
+            .unwrap();
+        engine.dispatch(Action::StartGame(variant)).unwrap();
+        engine
+    }
+
+    fn current(engine: &GameEngine) -> Question {
+        let session = engine.session().unwrap();
+        (session.target.clone(), session.options.clone())
+    }
+
+    /// Answers the rest of the first level correctly, returning each question as asked.
+    fn play_level(engine: &mut GameEngine) -> Vec<Question> {
+        let mut asked = Vec::new();
+        while let GameStatus::Playing = engine.status() {
+            let question = current(engine);
+            engine.dispatch(Action::Submit(question.0.clone())).unwrap();
+            engine.dispatch(Action::Continue).unwrap();
+            asked.push(question);
+        }
+        asked
+    }
+
+    #[test]
+    fn same_seed_asks_same_questions() {
+        let greek = GameSettings {
+            alphabet: Alphabet::Greek,
+            ..GameSettings::default()
+        };
+        for (settings, variant) in [
+            (GameSettings::default(), GameVariant::Letters),
+            (GameSettings::default(), GameVariant::Maths),
+            (GameSettings::default(), GameVariant::Sequence),
+            (greek, GameVariant::UpperToLower),
+        ] {
+            let first = play_level(&mut start(settings.clone(), 7, variant.clone()));
+            let second = play_level(&mut start(settings, 7, variant));
+            assert!(!first.is_empty());
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn different_seeds_ask_different_questions() {
+        let first = play_level(&mut start(GameSettings::default(), 1, GameVariant::Letters));
+        let second = play_level(&mut start(GameSettings::default(), 2, GameVariant::Letters));
+        assert_ne!(first, second);
+    }
+
+    /// Level 3 shows five options, enough to take some away.
+    fn five_options() -> GameSettings {
+        GameSettings {
+            start_level: 3,
+            ..GameSettings::default()
+        }
+    }
+
+    #[test]
+    fn hints_leave_later_questions_alone() {
+        let plain = play_level(&mut start(five_options(), 7, GameVariant::Letters));
+
+        let mut hinted = start(five_options(), 7, GameVariant::Letters);
+        hinted.dispatch(Action::RequestHint).unwrap();
+        let with_hint = play_level(&mut hinted);
+
+        assert_eq!(with_hint[0].1.len(), plain[0].1.len() - 1);
+        assert_eq!(with_hint[1..], plain[1..]);
+    }
+
+    #[test]
+    fn retries_leave_later_questions_alone() {
+        let settings = GameSettings {
+            retry_policy: RetryPolicy::RetryWithFewerOptions { attempts: 1 },
+            ..five_options()
+        };
+        let plain = play_level(&mut start(settings.clone(), 7, GameVariant::Letters));
+
+        let mut retried = start(settings, 7, GameVariant::Letters);
+        let (target, options) = current(&retried);
+        let wrong = options.into_iter().find(|o| *o != target).unwrap();
+        retried.dispatch(Action::Submit(wrong)).unwrap();
+        retried.dispatch(Action::Continue).unwrap();
+        let after_retry = play_level(&mut retried);
+
+        assert_eq!(after_retry[0].0, plain[0].0);
+        assert_eq!(after_retry[0].1.len(), plain[0].1.len() - 1);
+        assert_eq!(after_retry[1..], plain[1..]);
+    }
+
+    #[test]
+    fn spoken_hints_share_the_replay_cooldown() {
+        let settings = GameSettings {
+            hint_style: HintStyle::ReplayPrompt,
+            ..GameSettings::default()
+        };
+        let mut engine = start(settings, 7, GameVariant::Letters);
+        engine
+            .dispatch(Action::Tick(REPLAY_COOLDOWN_SECONDS))
+            .unwrap();
+        engine.drain_events();
+        for _ in 0..3 {
+            engine.dispatch(Action::RequestHint).unwrap();
+            engine.dispatch(Action::ReplayPrompt).unwrap();
+        }
+        let said = engine
+            .drain_events()
+            .into_iter()
+            .filter(|e| matches!(e, EngineEvent::Sound(_)))
+            .count();
+        assert_eq!(said, 1);
+        assert_eq!(engine.session().unwrap().hints_used, 3);
+    }
+
+    #[test]
+    fn timeouts_count_as_answered() {
+        // The boss level is timed
+        let settings = GameSettings {
+            start_level: 4,
+            ..GameSettings::default()
+        };
+        let mut engine = start(settings, 7, GameVariant::Letters);
+        let limit = engine.session().unwrap().level_time_limit.unwrap();
+        engine.dispatch(Action::Tick(limit)).unwrap();
+        assert!(matches!(
+            engine.status(),
+            GameStatus::Feedback { success: false, .. }
+        ));
+        let stats = &engine.profile().unwrap().stats;
+        assert_eq!((stats.questions_answered, stats.correct_answers), (1, 0));
+    }
+
+    #[test]
+    fn players_can_go_back_to_device_settings() {
+        let defaults = GameSettings {
+            start_level: 2,
+            ..GameSettings::default()
+        };
+        let mut engine = GameEngine::new_with_settings(defaults.clone());
+        engine
+            .dispatch(Action::SelectProfile(Profile::new("Sam", "🦊", 0)))
+            .unwrap();
+        engine.dispatch(Action::OpenSettings).unwrap();
+        let own = GameSettings {
+            start_level: 3,
+            ..defaults.clone()
+        };
+        engine
+            .dispatch(Action::UpdateSettings(own.clone()))
+            .unwrap();
+        assert_eq!(engine.profile().unwrap().settings, Some(own));
+
+        engine.dispatch(Action::UseDefaultSettings).unwrap();
+        assert_eq!(*engine.settings(), defaults);
+        assert_eq!(engine.profile().unwrap().settings, None);
+    }
+
+    #[test]
+    fn continue_while_playing_is_refused() {
+        let mut engine = start(GameSettings::default(), 7, GameVariant::Letters);
+        let before = current(&engine);
+        let err = engine.dispatch(Action::Continue).unwrap_err();
+        assert!(matches!(err, EngineError::InvalidTransition { .. }));
+        assert_eq!(*engine.status(), GameStatus::Playing);
+        assert_eq!(current(&engine), before);
+    }
+
+    #[test]
+    fn pause_while_paused_is_refused() {
+        let mut engine = start(GameSettings::default(), 7, GameVariant::Letters);
+        engine.dispatch(Action::Pause).unwrap();
+        let err = engine.dispatch(Action::Pause).unwrap_err();
+        assert!(matches!(err, EngineError::InvalidTransition { .. }));
+        engine.dispatch(Action::Resume).unwrap();
+        assert_eq!(*engine.status(), GameStatus::Playing);
+    }
+
+    #[test]
+    fn invalid_settings_are_refused() {
+        let mut engine = GameEngine::new();
+        engine
+            .dispatch(Action::SelectProfile(Profile::new("Sam", "🦊", 0)))
+            .unwrap();
+        engine.dispatch(Action::OpenSettings).unwrap();
+        let settings = GameSettings {
+            hint_penalty: 2.0,
+            ..GameSettings::default()
+        };
+        let err = engine
+            .dispatch(Action::UpdateSettings(settings))
+            .unwrap_err();
+        assert!(matches!(err, EngineError::InvalidSettings(_)));
+        assert_eq!(*engine.settings(), GameSettings::default());
+    }
 }
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const ENGLISH: &str = include_str!("../data/messages/en.json");
+const SPANISH: &str = include_str!("../data/messages/es.json");
+const GERMAN: &str = include_str!("../data/messages/de.json");
+
+/// Language feedback is shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Spanish, Language::German];
+
+    /// Name shown in settings, written in the language itself.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// The built-in catalogue, parsed on first use.
+    pub fn catalogue(&self) -> &'static Catalogue {
+        static ENGLISH_CATALOGUE: OnceLock<Catalogue> = OnceLock::new();
+        static SPANISH_CATALOGUE: OnceLock<Catalogue> = OnceLock::new();
+        static GERMAN_CATALOGUE: OnceLock<Catalogue> = OnceLock::new();
+        let (cell, source) = match self {
+            Language::English => (&ENGLISH_CATALOGUE, ENGLISH),
+            Language::Spanish => (&SPANISH_CATALOGUE, SPANISH),
+            Language::German => (&GERMAN_CATALOGUE, GERMAN),
+        };
+        cell.get_or_init(|| Catalogue::parse(source).expect("built-in catalogue is valid"))
+    }
+}
+
+/// A message to show the player: a catalogue key and the values for its
+/// `{placeholders}`. Frontends render it with `Catalogue::render`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub key: String,
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+}
+
+impl Message {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a value for `{name}`. A `count` argument also picks the plural
+    /// form (`<key>_one` or `<key>_other`) where the catalogue has one.
+    pub fn arg(mut self, name: &str, value: impl ToString) -> Self {
+        self.args.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Message texts keyed by name, loaded from a flat JSON object such as
+/// `{ "times_up": "Time's Up!" }`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Catalogue {
+    texts: BTreeMap<String, String>,
+}
+
+impl Catalogue {
+    pub fn parse(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            texts: serde_json::from_str(json)?,
+        })
+    }
+
+    /// The text for `message` with its arguments filled in. Keys missing here
+    /// fall back to English, then to the key itself.
+    pub fn render(&self, message: &Message) -> String {
+        let plural = message.args.get("count").map(|count| {
+            let form = if count == "1" { "one" } else { "other" };
+            format!("{}_{}", message.key, form)
+        });
+        let english = Language::English.catalogue();
+        let text = [self, english]
+            .iter()
+            .find_map(|catalogue| {
+                plural
+                    .as_ref()
+                    .and_then(|key| catalogue.texts.get(key))
+                    .or_else(|| catalogue.texts.get(&message.key))
+            })
+            .unwrap_or(&message.key);
+
+        message
+            .args
+            .iter()
+            .fold(text.clone(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_filled_in() {
+        let message = Message::new("sum")
+            .arg("expression", "2 + 3")
+            .arg("answer", 5);
+        assert_eq!(Language::English.catalogue().render(&message), "2 + 3 = 5");
+    }
+
+    #[test]
+    fn count_picks_the_plural_form() {
+        let english = Language::English.catalogue();
+        let one = Message::new("counted_star").arg("count", 1);
+        let many = Message::new("counted_star").arg("count", 4);
+        let none = Message::new("counted_star").arg("count", 0);
+        assert_eq!(english.render(&one), "Yes! That's 1 star");
+        assert_eq!(english.render(&many), "Yes! That's 4 stars");
+        assert_eq!(english.render(&none), "Yes! That's 0 stars");
+    }
+
+    #[test]
+    fn missing_texts_fall_back_to_english_then_the_key() {
+        let partial = Catalogue::parse(r#"{ "great_job": "¡Muy bien!" }"#).unwrap();
+        assert_eq!(partial.render(&Message::new("great_job")), "¡Muy bien!");
+        assert_eq!(partial.render(&Message::new("times_up")), "Time's Up!");
+        assert_eq!(partial.render(&Message::new("no_such_key")), "no_such_key");
+    }
+
+    #[test]
+    fn translations_cover_every_english_key() {
+        let english: Vec<&String> = Language::English.catalogue().texts.keys().collect();
+        for language in Language::ALL {
+            let keys: Vec<&String> = language.catalogue().texts.keys().collect();
+            assert_eq!(keys, english, "{:?} catalogue keys differ", language);
+        }
+    }
+}
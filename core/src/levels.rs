@@ -0,0 +1,164 @@
+use crate::distractors::DistractorKind;
+use crate::error::EngineError;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+/// Describes a single level of a curriculum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSpec {
+    /// Number of questions asked before the level is scored.
+    pub questions: u32,
+    /// Number of options shown for each question (including the target).
+    pub options: usize,
+    /// Seconds allowed per question, None if no limit.
+    #[serde(default)]
+    pub time_limit: Option<f64>,
+    /// Fraction of questions (0.0 - 1.0) that must be answered correctly to pass.
+    #[serde(default = "default_pass_threshold")]
+    pub pass_threshold: f32,
+    /// Boss levels get the special presentation in the frontends.
+    #[serde(default)]
+    pub boss: bool,
+    /// How the wrong answers are chosen.
+    #[serde(default)]
+    pub distractors: DistractorKind,
+}
+
+fn default_pass_threshold() -> f32 {
+    0.8
+}
+
+impl LevelSpec {
+    /// Minimum score needed to pass this level.
+    pub fn required_score(&self) -> u32 {
+        (self.questions as f32 * self.pass_threshold).ceil() as u32
+    }
+}
+
+/// An ordered list of levels. Passing the last level completes the session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Curriculum {
+    pub levels: Vec<LevelSpec>,
+}
+
+impl Default for Curriculum {
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                LevelSpec {
+                    questions: 5,
+                    options: 2,
+                    time_limit: None,
+                    pass_threshold: default_pass_threshold(),
+                    boss: false,
+                    distractors: DistractorKind::Random,
+                },
+                LevelSpec {
+                    questions: 5,
+                    options: 3,
+                    time_limit: None,
+                    pass_threshold: default_pass_threshold(),
+                    boss: false,
+                    distractors: DistractorKind::Random,
+                },
+                LevelSpec {
+                    questions: 5,
+                    options: 5,
+                    time_limit: None,
+                    pass_threshold: default_pass_threshold(),
+                    boss: false,
+                    distractors: DistractorKind::Visual,
+                },
+                LevelSpec {
+                    questions: 1,
+                    options: 9,
+                    time_limit: Some(9.0),
+                    pass_threshold: default_pass_threshold(),
+                    boss: true,
+                    distractors: DistractorKind::Random,
+                },
+            ],
+        }
+    }
+}
+
+impl Curriculum {
+    /// Number of levels in the curriculum.
+    pub fn len(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Returns the spec for a 1-based level number, clamped to the available levels.
+    pub fn level(&self, level: u32) -> &LevelSpec {
+        let index = (level.max(1) as usize - 1).min(self.levels.len() - 1);
+        &self.levels[index]
+    }
+
+    /// Checks every level is playable.
+    pub fn validate(&self) -> std::result::Result<(), EngineError> {
+        if self.is_empty() {
+            return Err(EngineError::InvalidSettings(
+                "curriculum must contain at least one level".to_string(),
+            ));
+        }
+        for (i, spec) in self.levels.iter().enumerate() {
+            let level = i + 1;
+            if spec.questions == 0 {
+                return Err(EngineError::InvalidSettings(format!(
+                    "level {} must ask at least one question",
+                    level
+                )));
+            }
+            if spec.options == 0 {
+                return Err(EngineError::InvalidSettings(format!(
+                    "level {} must show at least one option",
+                    level
+                )));
+            }
+            if !(0.0..=1.0).contains(&spec.pass_threshold) {
+                return Err(EngineError::InvalidSettings(format!(
+                    "level {} pass threshold must be between 0 and 1",
+                    level
+                )));
+            }
+            if let Some(limit) = spec.time_limit {
+                if limit <= 0.0 {
+                    return Err(EngineError::InvalidSettings(format!(
+                        "level {} time limit must be positive",
+                        level
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether passing this level completes the session.
+    pub fn is_final(&self, level: u32) -> bool {
+        level >= self.len()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let curriculum: Self = serde_json::from_str(&content)?;
+        curriculum.validate()?;
+        Ok(curriculum)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
@@ -1,7 +1,35 @@
+pub mod action;
+pub mod adaptive;
+pub mod alphabet;
+pub mod distractors;
+pub mod error;
 pub mod game;
+pub mod i18n;
+pub mod levels;
+pub mod mastery;
+pub mod maths;
+pub mod phonics;
+pub mod profile;
+pub mod scheduler;
+pub mod sequence;
 pub mod settings;
 pub mod state;
+pub mod words;
 
+pub use action::Action;
+pub use adaptive::AdaptiveDifficulty;
+pub use alphabet::Alphabet;
+pub use distractors::{DistractorKind, DistractorStrategy};
+pub use error::EngineError;
 pub use game::GameEngine;
+pub use i18n::{Catalogue, Language, Message};
+pub use levels::{Curriculum, LevelSpec};
+pub use mastery::{CharacterStats, Mastery};
+pub use phonics::Phoneme;
+pub use profile::{Profile, ProfileStats, ProfileStore};
+pub use scheduler::Leitner;
 pub use settings::GameSettings;
-pub use state::{GameStatus, GameVariant, SessionState, SoundEvent};
+pub use state::{
+    CountObject, EngineEvent, GameStatus, GameVariant, QuestionShape, SessionState, SoundEvent,
+};
+pub use words::WordList;
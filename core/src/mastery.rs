@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How a child is doing with a single token (letter, number, ...).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CharacterStats {
+    pub attempts: u32,
+    pub correct: u32,
+    /// Sum of response times in seconds, kept as a total so it can be averaged
+    pub total_response_time: f64,
+}
+
+impl CharacterStats {
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.attempts as f32
+        }
+    }
+
+    pub fn mean_response_time(&self) -> Option<f64> {
+        (self.attempts > 0).then(|| self.total_response_time / self.attempts as f64)
+    }
+}
+
+/// Per-character results plus a confusion matrix of wrong answers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mastery {
+    /// Keyed by the target token
+    #[serde(default)]
+    pub characters: BTreeMap<String, CharacterStats>,
+    /// target -> wrongly chosen token -> number of times
+    #[serde(default)]
+    pub confusions: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl Mastery {
+    /// Records one answer. `chosen` is None when the question timed out.
+    pub fn record(&mut self, target: &str, chosen: Option<&str>, response_time: f64) {
+        let correct = chosen == Some(target);
+        let stats = self.characters.entry(target.to_string()).or_default();
+        stats.attempts += 1;
+        stats.total_response_time += response_time;
+        if correct {
+            stats.correct += 1;
+        } else if let Some(chosen) = chosen {
+            *self
+                .confusions
+                .entry(target.to_string())
+                .or_default()
+                .entry(chosen.to_string())
+                .or_default() += 1;
+        }
+    }
+
+    /// The most frequent (target, chosen, count) mix-ups, most common first.
+    pub fn top_confusions(&self, limit: usize) -> Vec<(&str, &str, u32)> {
+        let mut pairs: Vec<(&str, &str, u32)> = self
+            .confusions
+            .iter()
+            .flat_map(|(target, chosen)| {
+                chosen
+                    .iter()
+                    .map(move |(choice, &count)| (target.as_str(), choice.as_str(), count))
+            })
+            .collect();
+        pairs.sort_by_key(|p| std::cmp::Reverse(p.2));
+        pairs.truncate(limit);
+        pairs
+    }
+
+    /// Tokens with the lowest accuracy, weakest first.
+    pub fn weakest(&self, limit: usize) -> Vec<(&str, &CharacterStats)> {
+        let mut chars: Vec<(&str, &CharacterStats)> = self
+            .characters
+            .iter()
+            .map(|(c, s)| (c.as_str(), s))
+            .collect();
+        chars.sort_by(|a, b| a.1.accuracy().total_cmp(&b.1.accuracy()));
+        chars.truncate(limit);
+        chars
+    }
+}
@@ -0,0 +1,32 @@
+use crate::state::SoundEvent;
+use rand::Rng;
+
+/// Answers and every number in an expression stay within 0..=MAX.
+pub const MAX: u32 = 10;
+
+/// Builds an addition or subtraction, written like "2 + 3", whose answer is
+/// `answer`. Answers above `MAX` are clamped.
+pub fn expression_for<R: Rng + ?Sized>(answer: u32, rng: &mut R) -> String {
+    let answer = answer.min(MAX);
+    if rng.gen_bool(0.5) {
+        let left = rng.gen_range(0..=answer);
+        format!("{} + {}", left, answer - left)
+    } else {
+        let left = rng.gen_range(answer..=MAX);
+        format!("{} - {}", left, left - answer)
+    }
+}
+
+/// Reads an expression aloud as a run of number and operator words, e.g.
+/// "two", "plus", "three". Numbers use their own word recordings rather than
+/// the "Choose two" prompts.
+pub fn spoken(expression: &str) -> Vec<SoundEvent> {
+    expression
+        .split_whitespace()
+        .map(|part| match part {
+            "+" => SoundEvent::SayWord("plus".to_string()),
+            "-" => SoundEvent::SayWord("minus".to_string()),
+            number => SoundEvent::SayWord(number.to_string()),
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+/// A sound taught in phonics and the grapheme children pick for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phoneme {
+    /// Stable name for the sound, used for its recording (`phonemes/<id>.wav`)
+    pub id: &'static str,
+    /// How the sound is written, shown as the option and used as the target
+    pub grapheme: &'static str,
+}
+
+const fn phoneme(id: &'static str, grapheme: &'static str) -> Phoneme {
+    Phoneme { id, grapheme }
+}
+
+/// Early-years sounds with one grapheme each. Alternative spellings of the
+/// same sound (k, ck for /k/) are left out so a prompt never has two right answers.
+pub const PHONEMES: &[Phoneme] = &[
+    phoneme("s", "s"),
+    phoneme("a", "a"),
+    phoneme("t", "t"),
+    phoneme("p", "p"),
+    phoneme("i", "i"),
+    phoneme("n", "n"),
+    phoneme("m", "m"),
+    phoneme("d", "d"),
+    phoneme("g", "g"),
+    phoneme("o", "o"),
+    phoneme("k", "c"),
+    phoneme("e", "e"),
+    phoneme("u", "u"),
+    phoneme("r", "r"),
+    phoneme("h", "h"),
+    phoneme("b", "b"),
+    phoneme("f", "f"),
+    phoneme("l", "l"),
+    phoneme("j", "j"),
+    phoneme("v", "v"),
+    phoneme("w", "w"),
+    phoneme("ks", "x"),
+    phoneme("y", "y"),
+    phoneme("z", "z"),
+    phoneme("kw", "qu"),
+    phoneme("ch", "ch"),
+    phoneme("sh", "sh"),
+    phoneme("th", "th"),
+    phoneme("ng", "ng"),
+];
+
+/// The phoneme written as `grapheme`, if it is one we teach.
+pub fn by_grapheme(grapheme: &str) -> Option<&'static Phoneme> {
+    PHONEMES
+        .iter()
+        .find(|p| p.grapheme.eq_ignore_ascii_case(grapheme))
+}
@@ -0,0 +1,123 @@
+use crate::mastery::Mastery;
+use crate::scheduler::Leitner;
+use crate::settings::GameSettings;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+/// A child playing the game. Several profiles can share one device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub avatar: String,
+    /// Unix timestamp (seconds) supplied by the frontend when the profile was made
+    pub created_at: u64,
+    /// Replaces the device-wide settings while this profile is playing
+    #[serde(default)]
+    pub settings: Option<GameSettings>,
+    #[serde(default)]
+    pub stats: ProfileStats,
+    #[serde(default)]
+    pub mastery: Mastery,
+    /// Spaced-repetition boxes used when `QuestionSelection::SpacedRepetition` is on
+    #[serde(default)]
+    pub leitner: Leitner,
+}
+
+/// Running totals across every session a profile has played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileStats {
+    pub sessions_started: u32,
+    pub sessions_completed: u32,
+    pub questions_answered: u32,
+    /// Answered correctly at the first attempt
+    pub correct_answers: u32,
+    pub best_score: u32,
+    /// Answered correctly after one or more retries (see `RetryPolicy`)
+    #[serde(default)]
+    pub correct_after_retry: u32,
+    #[serde(default)]
+    pub hints_used: u32,
+}
+
+impl Profile {
+    pub fn new(name: &str, avatar: &str, created_at: u64) -> Self {
+        let slug: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+        Self {
+            id: format!("{}-{}", slug, created_at),
+            name: name.trim().to_string(),
+            avatar: avatar.to_string(),
+            created_at,
+            settings: None,
+            stats: ProfileStats::default(),
+            mastery: Mastery::default(),
+            leitner: Leitner::default(),
+        }
+    }
+}
+
+/// Somewhere profiles can be kept between runs.
+pub trait ProfileStore {
+    fn load_profiles(&self) -> Result<Vec<Profile>>;
+    /// Inserts the profile, or replaces the stored one with the same id.
+    fn save_profile(&mut self, profile: &Profile) -> Result<()>;
+    fn delete_profile(&mut self, id: &str) -> Result<()>;
+}
+
+/// Keeps every profile in a single JSON file. Used by the TUI and native builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct JsonFileProfileStore {
+    path: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonFileProfileStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    fn write_all(&self, profiles: &[Profile]) -> Result<()> {
+        let content = serde_json::to_string_pretty(profiles)?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ProfileStore for JsonFileProfileStore {
+    fn load_profiles(&self) -> Result<Vec<Profile>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        let profiles = serde_json::from_str(&content)?;
+        Ok(profiles)
+    }
+
+    fn save_profile(&mut self, profile: &Profile) -> Result<()> {
+        let mut profiles = self.load_profiles()?;
+        match profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+        self.write_all(&profiles)
+    }
+
+    fn delete_profile(&mut self, id: &str) -> Result<()> {
+        let mut profiles = self.load_profiles()?;
+        profiles.retain(|p| p.id != id);
+        self.write_all(&profiles)
+    }
+}
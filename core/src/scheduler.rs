@@ -0,0 +1,131 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Highest box a character can be promoted to.
+const MAX_BOX: u8 = 4;
+
+/// Characters never answered before are drawn as often as ones in this box.
+const UNSEEN_BOX: u8 = 1;
+
+/// Where a token sits in the Leitner system.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeitnerCard {
+    /// 0 is "keeps getting it wrong", `MAX_BOX` is "knows it well"
+    pub box_index: u8,
+    /// Value of `Leitner::clock` when the character was last asked
+    pub last_seen: u32,
+}
+
+/// Leitner-box spaced repetition fed by answer history.
+///
+/// A correct answer promotes a character one box, a wrong answer or timeout
+/// sends it back to box 0. Lower boxes, and characters not asked for a while,
+/// are more likely to be picked as the next target.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leitner {
+    #[serde(default)]
+    pub cards: BTreeMap<String, LeitnerCard>,
+    /// Number of answers recorded so far, used to measure how stale a card is
+    #[serde(default)]
+    pub clock: u32,
+}
+
+impl Leitner {
+    /// Moves `target` between boxes after it has been answered.
+    pub fn record(&mut self, target: &str, correct: bool) {
+        let card = self.cards.entry(target.to_string()).or_default();
+        card.box_index = if correct {
+            (card.box_index + 1).min(MAX_BOX)
+        } else {
+            0
+        };
+        card.last_seen = self.clock;
+        self.clock += 1;
+    }
+
+    /// Picks the next target from `pool`, favouring weak and stale characters.
+    pub fn choose<R: Rng + ?Sized>(&self, pool: &[String], rng: &mut R) -> Option<String> {
+        let weights: Vec<u32> = pool.iter().map(|t| self.weight(t, pool.len())).collect();
+        let dist = WeightedIndex::new(&weights).ok()?;
+        pool.get(dist.sample(rng)).cloned()
+    }
+
+    fn weight(&self, token: &str, pool_len: usize) -> u32 {
+        let (box_index, since_seen) = match self.cards.get(token) {
+            Some(card) => (card.box_index, self.clock.saturating_sub(card.last_seen)),
+            None => (UNSEEN_BOX, 0),
+        };
+        // Each box is drawn half as often as the one below it
+        let base = 1u32 << (MAX_BOX - box_index.min(MAX_BOX));
+        // Once a full pass of the pool has gone by without it, a card gets a boost
+        let pool_len = pool_len.max(1) as u32;
+        base * (1 + since_seen / pool_len).min(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn pool(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    /// Puts `token` in `box_index` by answering it right that many times.
+    fn learn(leitner: &mut Leitner, token: &str, box_index: u8) {
+        leitner.record(token, false);
+        for _ in 0..box_index {
+            leitner.record(token, true);
+        }
+    }
+
+    #[test]
+    fn answers_move_cards_between_boxes() {
+        let mut leitner = Leitner::default();
+        learn(&mut leitner, "A", MAX_BOX + 3);
+        assert_eq!(leitner.cards["A"].box_index, MAX_BOX);
+        leitner.record("A", false);
+        assert_eq!(leitner.cards["A"].box_index, 0);
+        assert_eq!(leitner.clock, u32::from(MAX_BOX) + 5);
+    }
+
+    #[test]
+    fn weak_cards_outweigh_known_ones() {
+        let mut leitner = Leitner::default();
+        learn(&mut leitner, "B", MAX_BOX);
+        learn(&mut leitner, "A", 0);
+        // A pool of 26 keeps the staleness boost out of it
+        assert_eq!(leitner.weight("A", 26), 16);
+        assert_eq!(leitner.weight("B", 26), 1);
+
+        let tokens = pool(&["A", "B"]);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let picked_a = (0..1000)
+            .filter(|_| leitner.choose(&tokens, &mut rng).unwrap() == "A")
+            .count();
+        assert!(picked_a > 850, "A picked {} times", picked_a);
+    }
+
+    #[test]
+    fn stale_cards_get_a_boost() {
+        let mut leitner = Leitner::default();
+        learn(&mut leitner, "A", MAX_BOX);
+        let fresh = leitner.weight("A", 3);
+        for _ in 0..9 {
+            leitner.record("B", true);
+        }
+        assert!(leitner.weight("A", 3) > fresh);
+        assert_eq!(leitner.weight("A", 3), 4);
+    }
+
+    #[test]
+    fn empty_pool_has_no_choice() {
+        let leitner = Leitner::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        assert_eq!(leitner.choose(&[], &mut rng), None);
+    }
+}
@@ -0,0 +1,37 @@
+use rand::Rng;
+
+/// Tokens shown in a sequencing question, including the gap.
+pub const RUN_LENGTH: usize = 4;
+
+/// A run of consecutive tokens from `pool` (in pool order) that contains
+/// `target`, with `target` replaced by the gap. The gap may fall anywhere,
+/// so the same shape covers "what comes next?" and "what's missing?".
+pub fn run_with_gap<R: Rng + ?Sized>(
+    pool: &[String],
+    target: &str,
+    rng: &mut R,
+) -> Vec<Option<String>> {
+    let Some(index) = pool.iter().position(|t| t == target) else {
+        return vec![None];
+    };
+    let length = RUN_LENGTH.min(pool.len());
+    // Earliest and latest starts that keep the run inside the pool and the target inside the run
+    let first = (index + 1).saturating_sub(length);
+    let last = index.min(pool.len() - length);
+    // Drawn as u32 so native and wasm32 builds take the same value from a seed
+    let start = rng.gen_range(first as u32..=last as u32) as usize;
+
+    pool[start..start + length]
+        .iter()
+        .map(|t| (t != target).then(|| t.clone()))
+        .collect()
+}
+
+/// The run as shown to the child, e.g. "C D _ F".
+pub fn written(items: &[Option<String>]) -> String {
+    items
+        .iter()
+        .map(|item| item.as_deref().unwrap_or("_"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
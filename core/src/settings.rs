@@ -1,5 +1,11 @@
+use crate::alphabet::Alphabet;
+use crate::error::EngineError;
+use crate::i18n::Language;
+use crate::levels::Curriculum;
+use crate::state::GameVariant;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::default::Default;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -14,7 +20,53 @@ pub enum InputMethod {
     Hybrid,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How the target for each question is picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuestionSelection {
+    /// Every character in the pool is equally likely
+    Uniform,
+    /// Leitner boxes favour characters the player gets wrong or hasn't seen lately.
+    /// Needs an active profile for its history; falls back to uniform without one.
+    SpacedRepetition,
+}
+
+/// What happens after a wrong answer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetryPolicy {
+    /// Show the right answer and go on to the next question
+    #[default]
+    MoveOn,
+    /// Ask the same question again, up to `attempts` more times
+    Retry { attempts: u32 },
+    /// As `Retry`, with one wrong option taken away each time
+    RetryWithFewerOptions { attempts: u32 },
+}
+
+impl RetryPolicy {
+    /// How many more goes a child gets after a wrong answer.
+    pub fn retries(&self) -> u32 {
+        match self {
+            RetryPolicy::MoveOn => 0,
+            RetryPolicy::Retry { attempts } | RetryPolicy::RetryWithFewerOptions { attempts } => {
+                *attempts
+            }
+        }
+    }
+}
+
+/// What asking for a hint does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintStyle {
+    /// Take away one wrong option
+    #[default]
+    RemoveOption,
+    /// Mark the answer (or the next letter of a word) for frontends to highlight
+    HighlightTarget,
+    /// Say the prompt again
+    ReplayPrompt,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameSettings {
     #[serde(default = "default_feedback_duration")]
     pub feedback_duration_seconds: u64,
@@ -27,6 +79,39 @@ pub struct GameSettings {
 
     #[serde(default = "default_start_level")]
     pub start_level: u32,
+
+    #[serde(default)]
+    pub curriculum: Curriculum,
+
+    #[serde(default = "default_question_selection")]
+    pub question_selection: QuestionSelection,
+
+    /// Adjust options and time limits during a session based on recent answers
+    #[serde(default)]
+    pub adaptive_difficulty: bool,
+
+    /// Named token pools for `GameVariant::Custom`, e.g. "this week" -> s a t p i n
+    #[serde(default)]
+    pub custom_pools: BTreeMap<String, Vec<String>>,
+
+    /// Alphabet for the letter variants; phonics and words stay English
+    #[serde(default)]
+    pub alphabet: Alphabet,
+
+    /// Language for feedback messages
+    #[serde(default)]
+    pub language: Language,
+
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+
+    #[serde(default)]
+    pub hint_style: HintStyle,
+
+    /// Credit taken off a question's answer for each hint used on it, 0 to 1.
+    /// Any penalty keeps a hinted answer from adding to `score`.
+    #[serde(default)]
+    pub hint_penalty: f32,
 }
 
 fn default_feedback_duration() -> u64 {
@@ -45,6 +130,10 @@ fn default_start_level() -> u32 {
     1
 }
 
+fn default_question_selection() -> QuestionSelection {
+    QuestionSelection::Uniform
+}
+
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
@@ -52,11 +141,97 @@ impl Default for GameSettings {
             show_target_visual: default_show_target(),
             input_method: default_input_method(),
             start_level: default_start_level(),
+            curriculum: Curriculum::default(),
+            question_selection: default_question_selection(),
+            adaptive_difficulty: false,
+            custom_pools: BTreeMap::new(),
+            alphabet: Alphabet::default(),
+            language: Language::default(),
+            retry_policy: RetryPolicy::default(),
+            hint_style: HintStyle::default(),
+            hint_penalty: 0.0,
         }
     }
 }
 
 impl GameSettings {
+    /// Checks the settings are usable before the engine adopts them.
+    pub fn validate(&self) -> std::result::Result<(), EngineError> {
+        self.curriculum.validate()?;
+        if self.start_level == 0 || self.start_level > self.curriculum.len() {
+            return Err(EngineError::InvalidSettings(format!(
+                "start level {} must be between 1 and {}",
+                self.start_level,
+                self.curriculum.len()
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.hint_penalty) {
+            return Err(EngineError::InvalidSettings(
+                "hint penalty must be between 0 and 1".to_string(),
+            ));
+        }
+        self.validate_custom_pools()
+    }
+
+    /// Every pool needs distinct, non-empty tokens, and enough of them to
+    /// fill the options of the busiest level.
+    fn validate_custom_pools(&self) -> std::result::Result<(), EngineError> {
+        let (busiest, options) = self
+            .curriculum
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(i, spec)| (i + 1, spec.options))
+            .max_by_key(|&(level, options)| (options, std::cmp::Reverse(level)))
+            .unwrap_or((1, 1));
+        for (name, tokens) in &self.custom_pools {
+            if name.trim().is_empty() {
+                return Err(EngineError::InvalidSettings(
+                    "custom pools must have a name".to_string(),
+                ));
+            }
+            if let Some(blank) = tokens.iter().position(|t| t.trim().is_empty()) {
+                return Err(EngineError::InvalidSettings(format!(
+                    "custom pool \"{}\" has a blank token at position {}",
+                    name,
+                    blank + 1
+                )));
+            }
+            if let Some((i, token)) = tokens
+                .iter()
+                .enumerate()
+                .find(|(i, t)| tokens[..*i].contains(t))
+            {
+                return Err(EngineError::InvalidSettings(format!(
+                    "custom pool \"{}\" lists \"{}\" more than once (position {})",
+                    name,
+                    token,
+                    i + 1
+                )));
+            }
+            if tokens.len() < options {
+                return Err(EngineError::InvalidSettings(format!(
+                    "custom pool \"{}\" has {} tokens but level {} shows {} options",
+                    name,
+                    tokens.len(),
+                    busiest,
+                    options
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The tokens `variant` draws its targets and options from.
+    pub fn pool_for(&self, variant: &GameVariant) -> std::result::Result<Vec<String>, EngineError> {
+        match variant {
+            GameVariant::Custom(name) => self.custom_pools.get(name).cloned().ok_or_else(|| {
+                EngineError::InvalidSettings(format!("no custom pool named \"{}\"", name))
+            }),
+            _ => Ok(variant.pool(self.alphabet)),
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         if !path.as_ref().exists() {
@@ -65,7 +240,8 @@ impl GameSettings {
             return Ok(defaults);
         }
         let content = fs::read_to_string(path)?;
-        let settings = serde_json::from_str(&content)?;
+        let settings: Self = serde_json::from_str(&content)?;
+        settings.validate()?;
         Ok(settings)
     }
 
@@ -76,3 +252,63 @@ impl GameSettings {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(settings: GameSettings) -> String {
+        match settings.validate() {
+            Err(EngineError::InvalidSettings(reason)) => reason,
+            other => panic!("expected InvalidSettings, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(GameSettings::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn start_level_must_exist() {
+        let settings = GameSettings {
+            start_level: 0,
+            ..GameSettings::default()
+        };
+        assert_eq!(rejected(settings), "start level 0 must be between 1 and 4");
+    }
+
+    #[test]
+    fn hint_penalty_must_be_a_fraction() {
+        let settings = GameSettings {
+            hint_penalty: 1.5,
+            ..GameSettings::default()
+        };
+        assert_eq!(rejected(settings), "hint penalty must be between 0 and 1");
+    }
+
+    #[test]
+    fn custom_pools_must_fill_every_level() {
+        let mut settings = GameSettings::default();
+        settings.custom_pools.insert(
+            "week".to_string(),
+            vec!["Mon".to_string(), "Tue".to_string()],
+        );
+        assert_eq!(
+            rejected(settings),
+            "custom pool \"week\" has 2 tokens but level 4 shows 9 options"
+        );
+    }
+
+    #[test]
+    fn custom_pools_must_not_repeat_tokens() {
+        let mut settings = GameSettings::default();
+        let mut tokens: Vec<String> = (1..=9).map(|n| n.to_string()).collect();
+        tokens.push("3".to_string());
+        settings.custom_pools.insert("digits".to_string(), tokens);
+        assert_eq!(
+            rejected(settings),
+            "custom pool \"digits\" lists \"3\" more than once (position 10)"
+        );
+    }
+}
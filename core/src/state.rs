@@ -1,34 +1,167 @@
+use crate::adaptive::AdaptiveDifficulty;
+use crate::alphabet::{self, Alphabet};
+use crate::i18n::Message;
+use crate::maths;
+use crate::phonics;
+use crate::profile::Profile;
+use crate::words::WordList;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameVariant {
     Numbers,
     Letters,
+    /// Recognise lowercase letters
+    Lowercase,
+    /// Shown an uppercase letter, find its lowercase partner
+    UpperToLower,
+    /// Shown a lowercase letter, find its uppercase partner
+    LowerToUpper,
+    /// Numbers 0-10
+    ZeroToTen,
+    /// Numbers 11-20, typed as two keystrokes
+    Teens,
+    /// Hear a letter sound (/s/, /sh/) and pick the grapheme that makes it
+    Phonics,
+    /// Spell a short word from the engine's `WordList`, one letter at a time
+    Words,
+    /// Hear a high-frequency word ("the", "said") and pick it from written words
+    SightWords,
+    /// Solve an addition or subtraction within 10 and pick the answer
+    Maths,
+    /// Count 1-10 objects and pick the matching numeral
+    Counting,
+    /// Fill the gap in a run of the alphabet, e.g. "C D _ F"
+    Sequence,
+    /// Recognise tokens from a pool named in `GameSettings::custom_pools`
+    Custom(String),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SoundEvent {
-    #[default]
-    None,
     PlaySuccess,
     PlayFailure,
-    SayPrompt(char),
+    SayPrompt(String),
+    /// "Find the partner of ..." for the matching variants, carrying the letter shown
+    SayMatchPrompt(String),
+    /// A letter sound for phonics, carrying the phoneme id from `phonics::PHONEMES`
+    SayPhoneme(String),
+    /// A whole word, e.g. the word to spell
+    SayWord(String),
     GameStart,
     LevelComplete,
 }
 
+/// Something frontends may want to react to, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EngineEvent {
+    Sound(SoundEvent),
+    StatusChanged(GameStatus),
+    ScoreChanged {
+        score: u32,
+        total_score: u32,
+    },
+    /// Emitted once per second during the final seconds of a timed question.
+    TimerWarning {
+        seconds_left: u32,
+    },
+    /// The active profile's stats or settings changed and should be persisted.
+    ProfileUpdated(Box<Profile>),
+}
+
+impl SoundEvent {
+    /// Sound file for this event, relative to the sounds directory.
+    /// Lowercase prompts get their own recordings so case-insensitive
+    /// filesystems don't confuse `a.wav` with `A.wav`. Case is read from the
+    /// first letter, so the Welsh capital "Ll" is not mistaken for lowercase.
+    pub fn asset_path(&self) -> Option<String> {
+        match self {
+            SoundEvent::PlaySuccess => Some("success.wav".to_string()),
+            SoundEvent::PlayFailure => Some("failure.wav".to_string()),
+            SoundEvent::LevelComplete => Some("complete.wav".to_string()),
+            SoundEvent::SayPrompt(token) => Some(format!("prompts/{}.wav", prompt_name(token))),
+            SoundEvent::SayMatchPrompt(token) => {
+                Some(format!("prompts/match_{}.wav", prompt_name(token)))
+            }
+            SoundEvent::SayPhoneme(id) => Some(format!("phonemes/{}.wav", id)),
+            SoundEvent::SayWord(word) => Some(format!("words/{}.wav", word)),
+            SoundEvent::GameStart => None,
+        }
+    }
+}
+
+fn prompt_name(token: &str) -> String {
+    if token.chars().next().is_some_and(char::is_lowercase) {
+        format!("lower_{}", token)
+    } else {
+        token.to_string()
+    }
+}
+
 impl GameVariant {
-    /// Returns the character pool for this game variant
-    pub fn char_pool(&self) -> Vec<char> {
+    /// Returns the token pool for this game variant. These are the answers;
+    /// see `prompt_for` for what the child is shown. The letter variants use
+    /// `alphabet`. Custom pools live in the settings and come back empty here;
+    /// use `GameSettings::pool_for`.
+    pub fn pool(&self, alphabet: Alphabet) -> Vec<String> {
+        match self {
+            GameVariant::Numbers => (1..=9).map(|n: u32| n.to_string()).collect(),
+            GameVariant::Counting => (1..=10).map(|n: u32| n.to_string()).collect(),
+            GameVariant::ZeroToTen | GameVariant::Maths => {
+                (0..=maths::MAX).map(|n| n.to_string()).collect()
+            }
+            GameVariant::Teens => (11..=20).map(|n: u32| n.to_string()).collect(),
+            GameVariant::Letters | GameVariant::LowerToUpper | GameVariant::Sequence => {
+                alphabet.uppercase()
+            }
+            GameVariant::Lowercase | GameVariant::UpperToLower => alphabet.lowercase(),
+            GameVariant::Phonics => phonics::PHONEMES
+                .iter()
+                .map(|p| p.grapheme.to_string())
+                .collect(),
+            // Targets are words from the engine's word list; these are the letters
+            GameVariant::Words => ('a'..='z').map(String::from).collect(),
+            GameVariant::SightWords => WordList::sight_words().words().to_vec(),
+            GameVariant::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// The token presented for `target`: its case partner in the matching
+    /// variants, otherwise the target itself.
+    pub fn prompt_for(&self, target: &str) -> String {
         match self {
-            GameVariant::Numbers => ('1'..='9').collect(),
-            GameVariant::Letters => ('A'..='Z').collect(),
+            GameVariant::UpperToLower => alphabet::to_upper(target),
+            GameVariant::LowerToUpper => alphabet::fold(target),
+            _ => target.to_string(),
         }
     }
+
+    /// Whether the answer must match the target's case exactly.
+    pub fn case_sensitive(&self) -> bool {
+        matches!(
+            self,
+            GameVariant::Lowercase | GameVariant::UpperToLower | GameVariant::LowerToUpper
+        )
+    }
+
+    /// Whether `input` answers `target`, honouring `case_sensitive`.
+    pub fn matches(&self, input: &str, target: &str) -> bool {
+        if self.case_sensitive() {
+            input == target
+        } else {
+            alphabet::same_letter(input, target)
+        }
+    }
+
+    /// Whether the child matches a shown letter rather than a spoken one.
+    pub fn is_matching(&self) -> bool {
+        matches!(self, GameVariant::UpperToLower | GameVariant::LowerToUpper)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameStatus {
+    ProfileSelect,
     Menu,
     Settings {
         message: Option<String>,
@@ -36,7 +169,8 @@ pub enum GameStatus {
     Playing,
     Feedback {
         success: bool,
-        message: String,
+        /// Rendered in the player's language with `Catalogue::render`
+        message: Message,
     },
     LevelComplete {
         level: u32,
@@ -47,20 +181,122 @@ pub enum GameStatus {
         score: u32,
     },
     About,
+    /// Mastery report for the active profile
+    Progress,
+    /// Timers are frozen and the question hidden until `Action::Resume`
+    Paused {
+        /// The status to return to, `Playing` or `Feedback`
+        resume: Box<GameStatus>,
+    },
+}
+
+/// How the current question is answered.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QuestionShape {
+    /// Pick the target from the options in one go
+    #[default]
+    Choose,
+    /// Build the target word by picking options one letter at a time
+    Spell {
+        /// Letters picked so far, right or wrong
+        built: Vec<String>,
+    },
+    /// Count the objects shown and pick the matching numeral
+    Count {
+        quantity: u32,
+        object: CountObject,
+        /// Objects per row; frontends draw `quantity` objects in rows this wide
+        columns: u32,
+    },
+    /// Pick the token missing from a run, shown in pool order
+    Sequence {
+        /// The run with None where the gap is
+        items: Vec<Option<String>>,
+    },
+}
+
+/// What is drawn for a counting question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CountObject {
+    Star,
+    Rocket,
+    Moon,
+}
+
+impl CountObject {
+    pub const ALL: [CountObject; 3] = [CountObject::Star, CountObject::Rocket, CountObject::Moon];
+
+    /// Used in message keys such as `counted_star`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CountObject::Star => "star",
+            CountObject::Rocket => "rocket",
+            CountObject::Moon => "moon",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionState {
     pub variant: GameVariant,
+    #[serde(default)]
+    pub seed: u32, // Seed the question sequence was generated from
     pub current_level: u32,
+    #[serde(default)]
+    pub total_levels: u32,
+    #[serde(default)]
+    pub is_boss_level: bool,
     pub current_question_index: u32,
     pub total_questions: u32,
     pub score: u32,
+    #[serde(default)]
+    pub credit: f32, // Score plus partial credit for partly-spelled words; levels are passed on this
     pub total_score: u32, // Cumulative score across levels
-    pub target: char,
-    pub options: Vec<char>, // Visual hint options
+    pub target: String,
+    #[serde(default)]
+    pub prompt: String, // What is shown or said; differs from target when matching cases
+    pub options: Vec<String>, // Visual hint options
+    #[serde(default)]
+    pub question: QuestionShape,
     #[serde(default)]
     pub selected_index: usize, // For ArrowSelection mode
     pub level_time_limit: Option<f64>, // Seconds, None if no limit
-    pub level_elapsed_time: f64, // Seconds elapsed in current level
+    pub level_elapsed_time: f64,       // Seconds elapsed in current level
+    #[serde(default)]
+    pub question_elapsed_time: f64, // Seconds the current question has been shown
+    #[serde(default)]
+    pub attempt: u32, // Wrong answers to the current question; non-zero while a retry is due
+    #[serde(default)]
+    pub hints_used: u32, // Hints asked for on the current question
+    #[serde(default)]
+    pub highlighted: Option<String>, // Option a `HighlightTarget` hint points at
+    #[serde(default)]
+    pub prompt_replays: u32, // Times the current prompt has been replayed
+    #[serde(default)]
+    pub last_replay_at: Option<f64>, // `question_elapsed_time` of the last replay
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveDifficulty>, // None unless adaptive difficulty is on
+}
+
+impl SessionState {
+    /// Cues announcing the current question, played in order.
+    pub fn prompt_sounds(&self) -> Vec<SoundEvent> {
+        let sound = match self.variant {
+            GameVariant::UpperToLower | GameVariant::LowerToUpper => {
+                SoundEvent::SayMatchPrompt(self.prompt.clone())
+            }
+            GameVariant::Maths => return maths::spoken(&self.prompt),
+            GameVariant::Counting => SoundEvent::SayWord("how_many".to_string()),
+            GameVariant::Sequence => SoundEvent::SayWord("whats_missing".to_string()),
+            GameVariant::Words | GameVariant::SightWords => {
+                SoundEvent::SayWord(self.target.clone())
+            }
+            GameVariant::Phonics => match phonics::by_grapheme(&self.target) {
+                Some(phoneme) => SoundEvent::SayPhoneme(phoneme.id.to_string()),
+                None => SoundEvent::SayPrompt(self.target.clone()),
+            },
+            _ => SoundEvent::SayPrompt(self.target.clone()),
+        };
+        vec![sound]
+    }
 }
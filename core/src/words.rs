@@ -0,0 +1,68 @@
+use crate::error::EngineError;
+use anyhow::Result;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+/// Built-in list used when no other word file is supplied.
+const DEFAULT_WORDS: &str = include_str!("../data/cvc_words.txt");
+
+/// High-frequency words for the sight-word variant.
+const SIGHT_WORDS: &str = include_str!("../data/sight_words.txt");
+
+/// Words for the word-building and sight-word variants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        Self::parse(DEFAULT_WORDS).expect("built-in word list is valid")
+    }
+}
+
+impl WordList {
+    /// Reads one word per line, skipping blank lines and `#` comments.
+    /// Words are lowercased and must be plain ASCII letters.
+    pub fn parse(text: &str) -> std::result::Result<Self, EngineError> {
+        let words: Vec<String> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_ascii_lowercase)
+            .collect();
+        if words.is_empty() {
+            return Err(EngineError::InvalidSettings(
+                "word list must contain at least one word".to_string(),
+            ));
+        }
+        if let Some(bad) = words
+            .iter()
+            .find(|w| !w.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            return Err(EngineError::InvalidSettings(format!(
+                "word \"{}\" may only contain the letters a-z",
+                bad
+            )));
+        }
+        Ok(Self { words })
+    }
+
+    /// The built-in high-frequency words ("the", "said", ...).
+    pub fn sight_words() -> Self {
+        Self::parse(SIGHT_WORDS).expect("built-in sight word list is valid")
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::parse(&content)?)
+    }
+}
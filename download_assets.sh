@@ -1,24 +1,38 @@
 #!/bin/bash
 # Download assets from the latest GitHub release (v0.0.2)
-VERSION="v0.0.2"
+source "$(dirname "$0")/sound_assets.sh"
+
+VERSION="${VERSION:-v0.0.2}"
 BASE_URL="https://github.com/Robinlovelace/letterlanders/releases/download/$VERSION"
 
-mkdir -p app/static/sounds/prompts
+mkdir -p app/static/sounds/prompts app/static/sounds/phonemes app/static/sounds/words
 
-echo "Downloading Sound Assets for $VERSION..."
+# Release files are flat: prompts keep their bare name ("A.wav"), other
+# folders are prefixed ("words_cat.wav"). Names are percent-encoded for the URL.
+release_name() {
+    local name="${1#prompts/}"
+    echo "${name//\//_}"
+}
 
-# Core Sounds
-curl -L -o app/static/sounds/success.wav "$BASE_URL/success.wav"
-curl -L -o app/static/sounds/failure.wav "$BASE_URL/failure.wav"
-curl -L -o app/static/sounds/complete.wav "$BASE_URL/complete.wav"
+urlencode() {
+    local LC_ALL=C i c
+    for ((i = 0; i < ${#1}; i++)); do
+        c="${1:i:1}"
+        case "$c" in
+            [a-zA-Z0-9._-]) printf '%s' "$c" ;;
+            *) printf '%%%02X' "'$c" ;;
+        esac
+    done
+}
 
-# Prompts
-for i in {1..9}; do
-    curl -L -o "app/static/sounds/prompts/$i.wav" "$BASE_URL/$i.wav"
-done
+echo "Downloading Sound Assets for $VERSION..."
 
-for char in {A..Z}; do
-    curl -L -o "app/static/sounds/prompts/$char.wav" "$BASE_URL/$char.wav"
-done
+missing=0
+while IFS='|' read -r path _; do
+    if ! curl -sfL -o "app/static/sounds/$path" "$BASE_URL/$(urlencode "$(release_name "$path")")"; then
+        echo "Not in release $VERSION: $path (run generate_assets.sh to make it)"
+        missing=$((missing + 1))
+    fi
+done < <(sound_assets)
 
-echo "Assets downloaded to app/static/sounds/"
+echo "Assets downloaded to app/static/sounds/ ($missing missing)"
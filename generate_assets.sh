@@ -1,19 +1,12 @@
 #!/bin/bash
-echo "Generating sound assets..."
+source "$(dirname "$0")/sound_assets.sh"
 
-# Core Feedback Sounds
-espeak -w assets/sounds/success.wav "Great job!"
-espeak -w assets/sounds/failure.wav "Oops, try again."
-espeak -w assets/sounds/complete.wav "Session complete! You are amazing."
+echo "Generating sound assets..."
 
-# Number Prompts (1-9)
-for i in {1..9}; do
-    espeak -w "assets/sounds/prompts/$i.wav" "Choose $i"
-done
+mkdir -p assets/sounds/prompts assets/sounds/phonemes assets/sounds/words
 
-# Letter Prompts (A-Z)
-for char in {A..Z}; do
-    espeak -w "assets/sounds/prompts/$char.wav" "Choose $char"
+sound_assets | while IFS='|' read -r path voice text; do
+    espeak -v "$voice" -w "assets/sounds/$path" "$text" </dev/null
 done
 
 echo "Done! Assets generated in assets/sounds/"
@@ -0,0 +1,100 @@
+#!/bin/bash
+# Every sound file the engine can ask for (see SoundEvent::asset_path), shared by
+# generate_assets.sh and download_assets.sh. Each line of `sound_assets` is
+#   <path under sounds/>|<espeak voice>|<text to speak>
+# Keep the letter lists in step with core/src/alphabet.rs, the phonemes with
+# core/src/phonics.rs and the words with core/data/*.txt. Tokens from custom
+# pools and user-supplied word lists aren't known here and need their own files.
+
+# Upper-casing Greek and Cyrillic needs a UTF-8 locale
+export LC_ALL=C.UTF-8
+
+SOUND_ASSETS_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+
+# Latin letters beyond English a-z: Welsh digraphs, then Spanish and German
+EXTRA_LATIN_LETTERS="cy:ch cy:dd cy:ff cy:ng cy:ll cy:ph cy:rh cy:th es:ñ de:ä de:ö de:ü de:ß"
+
+GREEK_LETTERS="α β γ δ ε ζ η θ ι κ λ μ ν ξ ο π ρ σ τ υ φ χ ψ ω"
+
+RUSSIAN_LETTERS="а б в г д е ё ж з и й к л м н о п р с т у ф х ц ч ш щ ъ ы ь э ю я"
+
+# Phoneme id and the espeak phoneme mnemonic that says it
+PHONEMES="s:[[s:]] a:[[a]] t:[[t]] p:[[p]] i:[[I]] n:[[n:]] m:[[m:]] d:[[d]] g:[[g]]
+o:[[0]] k:[[k]] e:[[E]] u:[[V]] r:[[r]] h:[[h]] b:[[b]] f:[[f:]] l:[[l:]] j:[[dZ]]
+v:[[v:]] w:[[w]] ks:[[ks]] y:[[j]] z:[[z:]] kw:[[kw]] ch:[[tS]] sh:[[S:]] th:[[T:]] ng:[[N:]]"
+
+# Capital form as printed on a letter card (alphabet::to_upper): ß becomes ẞ
+# and digraphs only capitalise their first letter
+upper() {
+    if [ "$1" = "ß" ]; then echo "ẞ"; else echo "${1^}"; fi
+}
+
+# Prompts for one letter in each case, for the letter and case-matching variants
+letter_assets() {
+    local voice="$1" lower="$2" capital
+    capital="$(upper "$lower")"
+    if [ "$voice" = "en" ]; then
+        echo "prompts/$capital.wav|en|Choose $capital"
+        echo "prompts/lower_$lower.wav|en|Choose small $lower"
+        echo "prompts/match_$capital.wav|en|Find the small letter for $capital"
+        echo "prompts/match_lower_$lower.wav|en|Find the capital letter for $lower"
+    else
+        # An English voice can't name these letters, so they are said on their own
+        echo "prompts/$capital.wav|$voice|$capital"
+        echo "prompts/lower_$lower.wav|$voice|$lower"
+        echo "prompts/match_$capital.wav|$voice|$capital"
+        echo "prompts/match_lower_$lower.wav|$voice|$lower"
+    fi
+}
+
+word_list() {
+    tr -d '\r' <"$SOUND_ASSETS_DIR/core/data/$1" | sed 's/[[:space:]]//g' |
+        grep -v -e '^#' -e '^$' | tr '[:upper:]' '[:lower:]'
+}
+
+sound_assets() {
+    # Core feedback sounds
+    echo "success.wav|en|Great job!"
+    echo "failure.wav|en|Oops, try again."
+    echo "complete.wav|en|Session complete! You are amazing."
+
+    # Number prompts
+    for i in {0..20}; do
+        echo "prompts/$i.wav|en|Choose $i"
+    done
+
+    for char in {a..z}; do
+        letter_assets en "$char"
+    done
+    for entry in $EXTRA_LATIN_LETTERS; do
+        letter_assets "${entry%%:*}" "${entry#*:}"
+    done
+    for letter in $GREEK_LETTERS; do
+        letter_assets el "$letter"
+    done
+    for letter in $RUSSIAN_LETTERS; do
+        letter_assets ru "$letter"
+    done
+
+    for entry in $PHONEMES; do
+        echo "phonemes/${entry%%:*}.wav|en|${entry#*:}"
+    done
+
+    # Spoken cues for sums, counting and sequences. Sums are read as number
+    # words up to maths::MAX
+    for i in {0..10}; do
+        echo "words/$i.wav|en|$i"
+    done
+    echo "words/plus.wav|en|plus"
+    echo "words/minus.wav|en|minus"
+    echo "words/how_many.wav|en|How many?"
+    echo "words/whats_missing.wav|en|What's missing?"
+
+    # Words to build and sight words, with duplicates between the lists dropped
+    {
+        word_list cvc_words.txt
+        word_list sight_words.txt
+    } | sort -u | while read -r word; do
+        echo "words/$word.wav|en|$word"
+    done
+}
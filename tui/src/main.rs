@@ -1,20 +1,39 @@
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, DisableFocusChange, EnableFocusChange, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use letterlanders_core::settings::InputMethod;
-use letterlanders_core::{GameEngine, GameSettings, GameStatus, GameVariant, SoundEvent};
+use letterlanders_core::profile::JsonFileProfileStore;
+use letterlanders_core::settings::{HintStyle, InputMethod, QuestionSelection, RetryPolicy};
+use letterlanders_core::{
+    Action, Alphabet, EngineEvent, GameEngine, GameSettings, GameStatus, GameVariant, Language,
+    Profile, ProfileStore, SessionState, WordList,
+};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 mod ui;
 
+const AVATARS: [&str; 6] = ["rocket", "star", "moon", "comet", "planet", "alien"];
+const MAX_NAME_LEN: usize = 16;
+
+/// Frontend state for the profile picker screen.
+pub struct ProfilePicker {
+    pub profiles: Vec<Profile>,
+    /// Name being typed for a new profile, None when not creating one
+    pub new_name: Option<String>,
+}
+
 fn main() -> Result<()> {
-    // Load Settings
-    let settings = GameSettings::load_from_file("settings.json").unwrap_or_default();
+    // Load Settings. A settings file that fails validation (say, a custom pool
+    // too small for the curriculum) stops here with the reason rather than
+    // being silently replaced by the defaults.
+    let settings = GameSettings::load_from_file("settings.json")?;
+
+    // Optional `--seed <n>` replays the same question sequence every session
+    let seed = parse_seed(std::env::args().skip(1));
 
     // Audio Setup
     // We use a result here because on some headless CI/servers audio might fail,
@@ -33,15 +52,20 @@ fn main() -> Result<()> {
     // Terminal Setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    // Focus events let the game pause itself when the terminal is left
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app_result = run_app(&mut terminal, sink.as_ref(), settings);
+    let app_result = run_app(&mut terminal, sink.as_ref(), settings, seed);
 
     // Restore Terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
     app_result
@@ -51,189 +75,219 @@ fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     sink: Option<&rodio::Sink>,
     settings: GameSettings,
+    seed: Option<u32>,
 ) -> Result<()> {
     let mut engine = GameEngine::new_with_settings(settings);
-    let mut feedback_start: Option<Instant> = None;
+    engine.set_seed(seed);
+    // An optional words.txt replaces the built-in word-building list
+    if let Ok(words) = WordList::load_from_file("words.txt") {
+        engine.set_word_list(words);
+    }
+    let mut store = JsonFileProfileStore::new("profiles.json");
+    let mut picker = ProfilePicker {
+        profiles: store.load_profiles().unwrap_or_default(),
+        new_name: None,
+    };
+    // Keystrokes of a multi-character answer such as "14", not yet submitted
+    let mut typed = String::new();
+    // How long the current feedback has been on screen, not counting pauses
+    let mut feedback_shown: Option<Duration> = None;
     let mut last_tick = Instant::now();
 
     loop {
-        terminal.draw(|f| ui::draw(f, &engine))?;
+        terminal.draw(|f| ui::draw(f, &engine, &picker, &typed))?;
 
-        // Game Timer Tick (for Boss Level)
+        // Game Timer Tick (boss countdown and response times)
         let now = Instant::now();
-        let dt = now.duration_since(last_tick).as_secs();
-        if dt >= 1 {
-            engine.tick(dt as f64);
-            last_tick = now;
-        }
-
-        // Handle Audio
-        let sound = engine.consume_sound();
-        if let Some(s) = sink {
-            match sound {
-                SoundEvent::None => {}
-                SoundEvent::PlaySuccess => play_file(s, "success.wav"),
-                SoundEvent::PlayFailure => play_file(s, "failure.wav"),
-                SoundEvent::SayPrompt(c) => play_file(s, &format!("prompts/{}.wav", c)),
-                SoundEvent::LevelComplete => play_file(s, "complete.wav"),
+        let elapsed = now.duration_since(last_tick);
+        let _ = engine.dispatch(Action::Tick(elapsed.as_secs_f64()));
+        last_tick = now;
+
+        // Handle Audio (the sink plays queued files back to back, preserving order)
+        for event in engine.drain_events() {
+            match (sink, event) {
+                (Some(s), EngineEvent::Sound(sound)) => {
+                    if let Some(path) = sound.asset_path() {
+                        play_file(s, &path);
+                    }
+                }
+                (_, EngineEvent::ProfileUpdated(profile)) => {
+                    let _ = store.save_profile(&profile);
+                }
                 _ => {}
             }
         }
 
-        // Auto-advance Feedback Timer
-        if let GameStatus::Feedback { .. } = engine.status {
-            if feedback_start.is_none() {
-                feedback_start = Some(Instant::now());
-            } else if let Some(start) = feedback_start {
-                if start.elapsed() >= Duration::from_secs(engine.settings.feedback_duration_seconds)
-                {
-                    engine.next_level();
-                    feedback_start = None;
+        // Auto-advance Feedback Timer. A pause holds the count (and any
+        // half-typed answer) until play resumes
+        match engine.status() {
+            GameStatus::Feedback { .. } => {
+                let shown = feedback_shown.unwrap_or_default() + elapsed;
+                if shown >= Duration::from_secs(engine.settings().feedback_duration_seconds) {
+                    let _ = engine.dispatch(Action::Continue);
+                    feedback_shown = None;
+                } else {
+                    feedback_shown = Some(shown);
                 }
             }
-        } else {
-            feedback_start = None;
+            GameStatus::Paused { .. } => {}
+            _ => feedback_shown = None,
+        }
+
+        if !matches!(
+            engine.status(),
+            GameStatus::Playing | GameStatus::Paused { .. }
+        ) {
+            typed.clear();
         }
 
         // Input
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            // Leaving the terminal mid-question (a doorbell) shouldn't cost the level
+            if matches!(event, Event::FocusLost) && engine.accepts(&Action::Pause) {
+                let _ = engine.dispatch(Action::Pause);
+            }
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
-                    match engine.status {
+                    let action = match engine.status() {
+                        GameStatus::ProfileSelect => {
+                            match profile_action(&mut picker, &mut store, key.code) {
+                                PickerInput::Select(profile) => {
+                                    Some(Action::SelectProfile(*profile))
+                                }
+                                PickerInput::Idle => None,
+                                PickerInput::Exit => return Ok(()),
+                            }
+                        }
                         GameStatus::Menu => match key.code {
                             KeyCode::Char('n') | KeyCode::Char('N') => {
-                                engine.start_game(GameVariant::Numbers)
+                                Some(Action::StartGame(GameVariant::Numbers))
                             }
                             KeyCode::Char('l') | KeyCode::Char('L') => {
-                                engine.start_game(GameVariant::Letters)
+                                Some(Action::StartGame(GameVariant::Letters))
+                            }
+                            KeyCode::Char('c') | KeyCode::Char('C') => {
+                                Some(Action::StartGame(GameVariant::Lowercase))
+                            }
+                            KeyCode::Char('m') | KeyCode::Char('M') => {
+                                Some(Action::StartGame(GameVariant::UpperToLower))
+                            }
+                            KeyCode::Char('b') | KeyCode::Char('B') => {
+                                Some(Action::StartGame(GameVariant::LowerToUpper))
+                            }
+                            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                                Some(Action::StartGame(GameVariant::ZeroToTen))
+                            }
+                            KeyCode::Char('t') | KeyCode::Char('T') => {
+                                Some(Action::StartGame(GameVariant::Teens))
                             }
-                            KeyCode::Char('s') | KeyCode::Char('S') => {
-                                engine.status = GameStatus::Settings { message: None }
+                            KeyCode::Char('f') | KeyCode::Char('F') => {
+                                Some(Action::StartGame(GameVariant::Phonics))
+                            }
+                            KeyCode::Char('w') | KeyCode::Char('W') => {
+                                Some(Action::StartGame(GameVariant::Words))
+                            }
+                            KeyCode::Char('h') | KeyCode::Char('H') => {
+                                Some(Action::StartGame(GameVariant::SightWords))
+                            }
+                            KeyCode::Char('a') | KeyCode::Char('A') => {
+                                Some(Action::StartGame(GameVariant::Maths))
+                            }
+                            KeyCode::Char('o') | KeyCode::Char('O') => {
+                                Some(Action::StartGame(GameVariant::Counting))
+                            }
+                            KeyCode::Char('e') | KeyCode::Char('E') => {
+                                Some(Action::StartGame(GameVariant::Sequence))
+                            }
+                            // Custom pools, numbered in the order the menu lists them
+                            KeyCode::Char(c @ '1'..='9') => engine
+                                .settings()
+                                .custom_pools
+                                .keys()
+                                .nth(c as usize - '1' as usize)
+                                .map(|name| Action::StartGame(GameVariant::Custom(name.clone()))),
+                            KeyCode::Char('s') | KeyCode::Char('S') => Some(Action::OpenSettings),
+                            KeyCode::Char('r') | KeyCode::Char('R') => Some(Action::OpenProgress),
+                            KeyCode::Char('p') | KeyCode::Char('P') => {
+                                picker.profiles = store.load_profiles().unwrap_or_default();
+                                Some(Action::SwitchProfile)
                             }
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                            _ => {}
+                            _ => None,
                         },
-                        GameStatus::Settings { ref mut message } => {
-                            // ... existing settings input ...
-                            match key.code {
-                                KeyCode::Char('1') => {
-                                    engine.settings.show_target_visual =
-                                        !engine.settings.show_target_visual;
-                                    *message = None;
-                                }
-                                KeyCode::Char('2') => {
-                                    engine.settings.feedback_duration_seconds =
-                                        match engine.settings.feedback_duration_seconds {
-                                            1 => 2,
-                                            2 => 3,
-                                            3 => 5,
-                                            _ => 1,
-                                        };
-                                    *message = None;
-                                }
-                                KeyCode::Char('3') => {
-                                    engine.settings.input_method = match engine
-                                        .settings
-                                        .input_method
-                                    {
-                                        InputMethod::DirectKeyboard => InputMethod::ArrowSelection,
-                                        InputMethod::ArrowSelection => InputMethod::Hybrid,
-                                        InputMethod::Hybrid => InputMethod::DirectKeyboard,
-                                    };
-                                    *message = None;
-                                }
-                                KeyCode::Char('4') => {
-                                    engine.settings.start_level =
-                                        if engine.settings.start_level >= 4 {
-                                            1
-                                        } else {
-                                            engine.settings.start_level + 1
-                                        };
-                                    *message = None;
-                                }
-                                KeyCode::Char('5') => {
-                                    // Export
-                                    match engine
-                                        .settings
-                                        .save_to_file("letterlanders_settings_export.json")
-                                    {
-                                        Ok(_) => {
-                                            *message = Some(
-                                                "Exported to letterlanders_settings_export.json"
-                                                    .to_string(),
-                                            )
-                                        }
-                                        Err(e) => *message = Some(format!("Export failed: {}", e)),
-                                    }
-                                }
-                                KeyCode::Char('6') => {
-                                    // Import
-                                    match GameSettings::load_from_file(
-                                        "letterlanders_settings_export.json",
-                                    ) {
-                                        Ok(s) => {
-                                            engine.settings = s;
-                                            *message =
-                                                Some("Settings imported successfully!".to_string());
-                                        }
-                                        Err(_) => {
-                                            *message = Some(
-                                                "Import failed: file not found or invalid"
-                                                    .to_string(),
-                                            )
-                                        }
-                                    }
-                                }
-                                KeyCode::Esc => {
-                                    // Save settings when exiting menu
-                                    let _ = engine.settings.save_to_file("settings.json");
-                                    engine.status = GameStatus::Menu;
-                                }
-                                _ => {}
-                            }
+                        GameStatus::Settings { .. } => settings_action(engine.settings(), key.code),
+                        GameStatus::Playing | GameStatus::Feedback { .. }
+                            if key.code == KeyCode::Esc =>
+                        {
+                            Some(Action::Pause)
                         }
-                        GameStatus::Playing => match engine.settings.input_method {
+                        GameStatus::Playing if key.code == KeyCode::Tab => {
+                            Some(Action::RequestHint)
+                        }
+                        GameStatus::Playing if key.code == KeyCode::Char(' ') => {
+                            Some(Action::ReplayPrompt)
+                        }
+                        GameStatus::Playing => match engine.settings().input_method {
                             InputMethod::DirectKeyboard => match key.code {
-                                KeyCode::Char(c) => engine.submit_answer(c),
-                                KeyCode::Esc => engine.status = GameStatus::Menu,
-                                _ => {}
+                                KeyCode::Char(c) => type_answer(&mut typed, c, engine.session()),
+                                KeyCode::Backspace => {
+                                    typed.pop();
+                                    None
+                                }
+                                KeyCode::Enter if !typed.is_empty() => {
+                                    Some(Action::Submit(std::mem::take(&mut typed)))
+                                }
+                                _ => None,
                             },
                             InputMethod::ArrowSelection => match key.code {
-                                KeyCode::Left => engine.move_selection(-1),
-                                KeyCode::Right => engine.move_selection(1),
-                                KeyCode::Enter => engine.submit_current_selection(),
-                                KeyCode::Esc => engine.status = GameStatus::Menu,
-                                _ => {}
+                                KeyCode::Left => Some(Action::MoveSelection(-1)),
+                                KeyCode::Right => Some(Action::MoveSelection(1)),
+                                KeyCode::Enter => Some(Action::SubmitSelection),
+                                _ => None,
                             },
                             InputMethod::Hybrid => match key.code {
-                                KeyCode::Left => engine.move_selection(-1),
-                                KeyCode::Right => engine.move_selection(1),
-                                KeyCode::Enter => engine.submit_current_selection(),
-                                KeyCode::Char(c) => engine.submit_answer(c),
-                                KeyCode::Esc => engine.status = GameStatus::Menu,
-                                _ => {}
+                                KeyCode::Left => Some(Action::MoveSelection(-1)),
+                                KeyCode::Right => Some(Action::MoveSelection(1)),
+                                KeyCode::Enter if !typed.is_empty() => {
+                                    Some(Action::Submit(std::mem::take(&mut typed)))
+                                }
+                                KeyCode::Enter => Some(Action::SubmitSelection),
+                                KeyCode::Char(c) => type_answer(&mut typed, c, engine.session()),
+                                KeyCode::Backspace => {
+                                    typed.pop();
+                                    None
+                                }
+                                _ => None,
                             },
                         },
                         GameStatus::LevelComplete { .. } => match key.code {
-                            KeyCode::Enter => engine.advance_to_next_level_or_retry(),
-                            KeyCode::Esc => engine.status = GameStatus::Menu,
-                            _ => {}
+                            KeyCode::Enter => Some(Action::Continue),
+                            KeyCode::Esc => Some(Action::Quit),
+                            _ => None,
+                        },
+                        GameStatus::Feedback { .. } => None,
+                        GameStatus::Paused { .. } => match key.code {
+                            KeyCode::Enter | KeyCode::Char(' ') => Some(Action::Resume),
+                            KeyCode::Esc => Some(Action::Quit),
+                            _ => None,
                         },
-                        GameStatus::Feedback { .. } => {
-                            if key.code == KeyCode::Esc {
-                                engine.status = GameStatus::Menu
-                            }
-                        }
                         GameStatus::SessionComplete { .. } => match key.code {
                             KeyCode::Char('m') | KeyCode::Char('M') | KeyCode::Esc => {
-                                engine.status = GameStatus::Menu
+                                Some(Action::Quit)
                             }
-                            _ => {}
+                            _ => None,
                         },
-                        GameStatus::About => {
-                            if let KeyCode::Esc = key.code {
-                                engine.status = GameStatus::Menu;
-                            }
+                        GameStatus::About | GameStatus::Progress => match key.code {
+                            KeyCode::Esc => Some(Action::Quit),
+                            _ => None,
+                        },
+                    };
+
+                    if let Some(action) = action {
+                        if let Err(e) = engine.dispatch(action) {
+                            // Only the settings screen has room to report problems
+                            let _ = engine.dispatch(Action::ShowSettingsMessage(e.to_string()));
                         }
                     }
                 }
@@ -242,6 +296,186 @@ fn run_app(
     }
 }
 
+/// Adds a keystroke to the typed answer and submits it once no longer option
+/// could still match, so "1" waits for "14" but single letters go straight through.
+fn type_answer(typed: &mut String, c: char, session: Option<&SessionState>) -> Option<Action> {
+    typed.push(c);
+    // Compared by character so letters such as ñ or ß, which take more than
+    // one byte, line up with what has been typed
+    let typed_chars = typed.chars().count();
+    let waiting = session.is_some_and(|s| {
+        s.options.iter().any(|o| {
+            let start: String = o.chars().take(typed_chars).collect();
+            o.chars().count() > typed_chars && s.variant.matches(typed, &start)
+        })
+    });
+    if waiting {
+        None
+    } else {
+        Some(Action::Submit(std::mem::take(typed)))
+    }
+}
+
+enum PickerInput {
+    Select(Box<Profile>),
+    Idle,
+    Exit,
+}
+
+/// Handles a key press on the profile picker, creating profiles as needed.
+fn profile_action(
+    picker: &mut ProfilePicker,
+    store: &mut JsonFileProfileStore,
+    code: KeyCode,
+) -> PickerInput {
+    if let Some(name) = &mut picker.new_name {
+        match code {
+            KeyCode::Char(c) if (c.is_alphanumeric() || c == ' ') && name.len() < MAX_NAME_LEN => {
+                name.push(c)
+            }
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Enter if !name.trim().is_empty() => {
+                let created_at = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                let avatar = AVATARS[picker.profiles.len() % AVATARS.len()];
+                let profile = Profile::new(name, avatar, created_at);
+                let _ = store.save_profile(&profile);
+                picker.profiles.push(profile.clone());
+                picker.new_name = None;
+                return PickerInput::Select(Box::new(profile));
+            }
+            KeyCode::Esc => picker.new_name = None,
+            _ => {}
+        }
+        return PickerInput::Idle;
+    }
+
+    match code {
+        KeyCode::Char(c @ '1'..='9') => {
+            let index = c as usize - '1' as usize;
+            match picker.profiles.get(index) {
+                Some(profile) => PickerInput::Select(Box::new(profile.clone())),
+                None => PickerInput::Idle,
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            picker.new_name = Some(String::new());
+            PickerInput::Idle
+        }
+        KeyCode::Char('q') | KeyCode::Esc => PickerInput::Exit,
+        _ => PickerInput::Idle,
+    }
+}
+
+/// Maps a key press on the settings screen to an action, performing any file I/O first.
+/// Changes belong to the current player and are saved with their profile;
+/// the device defaults come from settings.json.
+fn settings_action(current: &GameSettings, code: KeyCode) -> Option<Action> {
+    let mut settings = current.clone();
+    match code {
+        KeyCode::Char('1') => {
+            settings.show_target_visual = !settings.show_target_visual;
+        }
+        KeyCode::Char('2') => {
+            settings.feedback_duration_seconds = match settings.feedback_duration_seconds {
+                1 => 2,
+                2 => 3,
+                3 => 5,
+                _ => 1,
+            };
+        }
+        KeyCode::Char('3') => {
+            settings.input_method = match settings.input_method {
+                InputMethod::DirectKeyboard => InputMethod::ArrowSelection,
+                InputMethod::ArrowSelection => InputMethod::Hybrid,
+                InputMethod::Hybrid => InputMethod::DirectKeyboard,
+            };
+        }
+        KeyCode::Char('4') => {
+            settings.start_level = if settings.start_level >= settings.curriculum.len() {
+                1
+            } else {
+                settings.start_level + 1
+            };
+        }
+        KeyCode::Char('7') => {
+            settings.question_selection = match settings.question_selection {
+                QuestionSelection::Uniform => QuestionSelection::SpacedRepetition,
+                QuestionSelection::SpacedRepetition => QuestionSelection::Uniform,
+            };
+        }
+        KeyCode::Char('8') => {
+            settings.adaptive_difficulty = !settings.adaptive_difficulty;
+        }
+        KeyCode::Char('9') => {
+            let next = Alphabet::ALL
+                .iter()
+                .position(|a| *a == settings.alphabet)
+                .map_or(0, |i| (i + 1) % Alphabet::ALL.len());
+            settings.alphabet = Alphabet::ALL[next];
+        }
+        KeyCode::Char('0') => {
+            let next = Language::ALL
+                .iter()
+                .position(|l| *l == settings.language)
+                .map_or(0, |i| (i + 1) % Language::ALL.len());
+            settings.language = Language::ALL[next];
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            settings.hint_style = match settings.hint_style {
+                HintStyle::RemoveOption => HintStyle::HighlightTarget,
+                HintStyle::HighlightTarget => HintStyle::ReplayPrompt,
+                HintStyle::ReplayPrompt => HintStyle::RemoveOption,
+            };
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            settings.retry_policy = match settings.retry_policy {
+                RetryPolicy::MoveOn => RetryPolicy::Retry { attempts: 1 },
+                RetryPolicy::Retry { attempts: 1 } => RetryPolicy::Retry { attempts: 2 },
+                RetryPolicy::Retry { .. } => RetryPolicy::RetryWithFewerOptions { attempts: 2 },
+                RetryPolicy::RetryWithFewerOptions { .. } => RetryPolicy::MoveOn,
+            };
+        }
+        KeyCode::Char('5') => {
+            // Export
+            let message = match settings.save_to_file("letterlanders_settings_export.json") {
+                Ok(_) => "Exported to letterlanders_settings_export.json".to_string(),
+                Err(e) => format!("Export failed: {}", e),
+            };
+            return Some(Action::ShowSettingsMessage(message));
+        }
+        KeyCode::Char('6') => {
+            // Import
+            return match GameSettings::load_from_file("letterlanders_settings_export.json") {
+                Ok(s) => Some(Action::UpdateSettings(s)),
+                Err(_) => Some(Action::ShowSettingsMessage(
+                    "Import failed: file not found or invalid".to_string(),
+                )),
+            };
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => return Some(Action::UseDefaultSettings),
+        KeyCode::Esc => return Some(Action::Quit),
+        _ => return None,
+    }
+    Some(Action::UpdateSettings(settings))
+}
+
+fn parse_seed(mut args: impl Iterator<Item = String>) -> Option<u32> {
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            return args.next().and_then(|s| s.parse().ok());
+        }
+        if let Some(value) = arg.strip_prefix("--seed=") {
+            return value.parse().ok();
+        }
+    }
+    None
+}
+
 fn play_file(sink: &rodio::Sink, filename: &str) {
     // Path relative to where binary is run (root of repo usually)
     let path = format!("assets/sounds/{}", filename);
@@ -1,14 +1,15 @@
-use letterlanders_core::settings::InputMethod;
-use letterlanders_core::{GameEngine, GameStatus};
+use crate::ProfilePicker;
+use letterlanders_core::settings::{HintStyle, InputMethod, QuestionSelection, RetryPolicy};
+use letterlanders_core::{CountObject, GameEngine, GameStatus, GameVariant, QuestionShape};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, engine: &GameEngine) {
+pub fn draw(f: &mut Frame, engine: &GameEngine, picker: &ProfilePicker, typed: &str) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -27,54 +28,132 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
 
-    match &engine.status {
+    match engine.status() {
+        GameStatus::ProfileSelect => {
+            let mut text = vec![Line::from("Who is playing?"), Line::from("")];
+
+            if let Some(name) = &picker.new_name {
+                text.push(Line::from(format!("New player name: {}_", name)));
+                text.push(Line::from(""));
+                text.push(Line::from("Press Enter to confirm, 'Esc' to cancel"));
+            } else {
+                if picker.profiles.is_empty() {
+                    text.push(Line::from("No players yet."));
+                }
+                for (i, profile) in picker.profiles.iter().take(9).enumerate() {
+                    text.push(Line::from(format!(
+                        "{}. [{}] {} - {} sessions, best score {}",
+                        i + 1,
+                        profile.avatar,
+                        profile.name,
+                        profile.stats.sessions_started,
+                        profile.stats.best_score
+                    )));
+                }
+                text.push(Line::from(""));
+                text.push(Line::from("Press 1-9 to choose a player"));
+                text.push(Line::from("Press 'N' for a New player"));
+                text.push(Line::from("Press 'Q' to Quit"));
+            }
+
+            let p = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title("Players"));
+            f.render_widget(p, chunks[1]);
+        }
         GameStatus::Menu => {
-            // ... existing menu code ...
-            let menu_text = vec![
-                Line::from("Welcome to LetterLanders!"),
-                Line::from(""),
-                Line::from("Press 'N' for Numbers"),
-                Line::from("Press 'L' for Letters"),
-                Line::from("Press 'S' for Settings"),
-                Line::from("Press 'Q' to Quit"),
+            // Laid out in columns so every key fits on an 80x24 terminal
+            let title = engine
+                .profile()
+                .map(|p| format!("Menu - Playing as {} [{}]", p.name, p.avatar))
+                .unwrap_or_else(|| "Menu".to_string());
+            let games = [
+                "[N] Numbers",
+                "[L] Letters",
+                "[C] lowercase",
+                "[Z] Zero to ten",
+                "[T] 11 to Twenty",
+                "[F] Phonics",
+                "[W] build Words",
+                "[H] sight words",
+                "[A] Adding",
+                "[O] count Objects",
+                "[E] what's nExt",
+                "[M] Match A to a",
+                "[B] match a to A",
             ];
+            let commands = ["[S] Settings", "[R] Report", "[P] Player", "[Q] Quit"];
+            let mut menu_text = vec![Line::from("Welcome to LetterLanders!")];
+            menu_text.extend(in_columns(&games, 4, 18));
+            menu_text.push(Line::from(""));
+            menu_text.extend(in_columns(&commands, 4, 18));
+            let pools = &engine.settings().custom_pools;
+            if !pools.is_empty() {
+                let sets: Vec<String> = pools
+                    .iter()
+                    .take(9)
+                    .enumerate()
+                    .map(|(i, (name, tokens))| {
+                        format!("[{}] {}: {}", i + 1, name, tokens.join(" "))
+                    })
+                    .collect();
+                menu_text.push(Line::from(""));
+                menu_text.push(Line::from("Your own sets:"));
+                menu_text.extend(in_columns(&sets, 3, 24));
+            }
             let p = Paragraph::new(menu_text)
                 .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).title("Menu"));
+                .block(Block::default().borders(Borders::ALL).title(title));
             f.render_widget(p, chunks[1]);
         }
         GameStatus::Settings { message } => {
-            let s = &engine.settings;
+            let s = engine.settings();
             let input_method_str = match s.input_method {
                 InputMethod::DirectKeyboard => "Direct Keyboard",
                 InputMethod::ArrowSelection => "Arrow Selection",
                 InputMethod::Hybrid => "Hybrid (Arrows + Type)",
             };
+            let retry_str = match s.retry_policy {
+                RetryPolicy::MoveOn => "none, move on".to_string(),
+                RetryPolicy::Retry { attempts } => format!("{} more", attempts),
+                RetryPolicy::RetryWithFewerOptions { attempts } => {
+                    format!("{} more, fewer options", attempts)
+                }
+            };
+            let hint_str = match s.hint_style {
+                HintStyle::RemoveOption => "Take away a wrong answer",
+                HintStyle::HighlightTarget => "Highlight the answer",
+                HintStyle::ReplayPrompt => "Say it again",
+            };
+            let selection_str = match s.question_selection {
+                QuestionSelection::Uniform => "Random",
+                QuestionSelection::SpacedRepetition => "Spaced Repetition",
+            };
 
-            let mut settings_text = vec![
-                Line::from("SETTINGS"),
-                Line::from(""),
-                Line::from(format!(
-                    "1. Show Target Visual: {} (Press '1' to toggle)",
-                    s.show_target_visual
-                )),
-                Line::from(format!(
-                    "2. Feedback Duration: {}s (Press '2' to cycle)",
-                    s.feedback_duration_seconds
-                )),
-                Line::from(format!(
-                    "3. Input Method: {} (Press '3' to toggle)",
-                    input_method_str
-                )),
-                Line::from(format!(
-                    "4. Start Level: {} (Press '4' to cycle 1-4)",
-                    s.start_level
-                )),
-                Line::from("5. Export Settings (Press '5' - shares config)"),
-                Line::from("6. Import Settings (Press '6' - note: not backwards compatible)"),
-                Line::from(""),
-                Line::from("Press 'Esc' to Save & Back"),
+            // Two columns keep the status line on screen at 80x24
+            let options = [
+                format!("[1] Target visual: {}", s.show_target_visual),
+                format!("[2] Feedback time: {}s", s.feedback_duration_seconds),
+                format!("[3] Input: {}", input_method_str),
+                format!(
+                    "[4] Start level: {} of {}",
+                    s.start_level,
+                    s.curriculum.len()
+                ),
+                "[5] Export settings".to_string(),
+                "[6] Import (not backward compatible)".to_string(),
+                format!("[7] Questions: {}", selection_str),
+                format!("[8] Adaptive difficulty: {}", s.adaptive_difficulty),
+                format!("[9] Alphabet: {}", s.alphabet.name()),
+                format!("[0] Language: {}", s.language.name()),
+                format!("[R] Retries: {}", retry_str),
+                format!("[H] Hints: {}", hint_str),
             ];
+            let mut settings_text = in_columns(&options, 2, 37);
+            settings_text.push(Line::from(""));
+            settings_text.push(Line::from(
+                "[D] Use the device defaults for this player   'Esc' to Save & Back",
+            ));
 
             if let Some(msg) = message {
                 settings_text.push(Line::from(""));
@@ -88,20 +167,17 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
 
             let p = Paragraph::new(settings_text)
                 .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
                 .block(Block::default().borders(Borders::ALL).title("Settings"));
             f.render_widget(p, chunks[1]);
         }
         GameStatus::Playing => {
-            if let Some(session) = &engine.session {
-                let mut content = vec![Line::from(format!(
-                    "Level {}/{}",
-                    session.current_level,
-                    if session.current_level > 3 {
-                        "BOSS"
-                    } else {
-                        "3"
-                    }
-                ))];
+            if let Some(session) = engine.session() {
+                let mut content = vec![Line::from(if session.is_boss_level {
+                    format!("Level {}/BOSS", session.current_level)
+                } else {
+                    format!("Level {}/{}", session.current_level, session.total_levels)
+                })];
 
                 if let Some(limit) = session.level_time_limit {
                     let remaining = (limit - session.level_elapsed_time).max(0.0);
@@ -111,15 +187,71 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                         Style::default()
                     };
                     content.push(Line::from(Span::styled(
-                        format!("TIME: {}s", remaining),
+                        format!("TIME: {}s", remaining.ceil()),
                         style,
                     )));
                 }
 
                 content.push(Line::from(""));
-                content.push(Line::from("Listen to the sound..."));
+                if session.variant.is_matching() {
+                    content.push(Line::from(Span::styled(
+                        format!("Find the partner of: {}", session.prompt),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )));
+                } else if session.variant == GameVariant::Maths {
+                    content.push(Line::from(Span::styled(
+                        format!("What is {} ?", session.prompt),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )));
+                } else {
+                    content.push(Line::from("Listen to the sound..."));
+                }
+                if let QuestionShape::Count {
+                    quantity,
+                    object,
+                    columns,
+                } = &session.question
+                {
+                    content.push(Line::from("How many can you count?"));
+                    let glyph = match object {
+                        CountObject::Star => "*",
+                        CountObject::Rocket => "^",
+                        CountObject::Moon => "o",
+                    };
+                    let glyphs = vec![glyph; *quantity as usize];
+                    for row in glyphs.chunks((*columns).max(1) as usize) {
+                        content.push(Line::from(Span::styled(
+                            row.join("  "),
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        )));
+                    }
+                }
+                if let QuestionShape::Sequence { items } = &session.question {
+                    content.push(Line::from("Which letter fills the gap?"));
+                    let run: Vec<&str> = items
+                        .iter()
+                        .map(|item| item.as_deref().unwrap_or("_"))
+                        .collect();
+                    content.push(Line::from(Span::styled(
+                        run.join("   "),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                }
+                if let QuestionShape::Spell { built } = &session.question {
+                    let slots: Vec<&str> = (0..session.target.chars().count())
+                        .map(|i| built.get(i).map_or("_", String::as_str))
+                        .collect();
+                    content.push(Line::from(Span::styled(
+                        format!("Spell it: {}", slots.join(" ")),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )));
+                }
 
-                match engine.settings.input_method {
+                match engine.settings().input_method {
                     InputMethod::DirectKeyboard => {
                         content.push(Line::from("Press the matching key on your keyboard!"));
                     }
@@ -132,33 +264,58 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                         content.push(Line::from("Type the character OR use Arrows to select!"));
                     }
                 }
+                content.push(Line::from(
+                    "Stuck? Press Tab for a hint, or Space to hear it again",
+                ));
                 content.push(Line::from(""));
 
-                if engine.settings.show_target_visual {
+                if engine.settings().show_target_visual {
                     content.insert(0, Line::from(format!("Target: {}", session.target)));
                 }
 
-                // Render Options
-                let mut options_spans = vec![];
+                // Render Options, wrapping onto more rows when words don't fit on one
+                let max_width = chunks[1].width.saturating_sub(4) as usize;
+                let mut rows: Vec<Vec<Span>> = vec![vec![]];
+                let mut row_width = 0;
                 for (i, option) in session.options.iter().enumerate() {
-                    let is_selected = (engine.settings.input_method == InputMethod::ArrowSelection
-                        || engine.settings.input_method == InputMethod::Hybrid)
+                    let is_selected = (engine.settings().input_method
+                        == InputMethod::ArrowSelection
+                        || engine.settings().input_method == InputMethod::Hybrid)
                         && i == session.selected_index;
 
                     let style = if is_selected {
                         Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                    } else if session.highlighted.as_ref() == Some(option) {
+                        Style::default()
+                            .fg(Color::Green)
+                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
                     } else {
                         Style::default()
                     };
 
-                    if i > 0 {
-                        options_spans.push(Span::raw("   "));
+                    let label = format!(" {} ", option);
+                    let width = label.chars().count();
+                    if row_width > 0 && row_width + 3 + width > max_width {
+                        rows.push(vec![]);
+                        row_width = 0;
+                    }
+                    let row = rows.last_mut().expect("rows starts non-empty");
+                    if row_width > 0 {
+                        row.push(Span::raw("   "));
+                        row_width += 3;
                     }
-                    options_spans.push(Span::styled(format!(" {} ", option), style));
+                    row.push(Span::styled(label, style));
+                    row_width += width;
+                }
+                for row in rows {
+                    content.push(Line::from(row));
+                }
+                if !typed.is_empty() {
+                    content.push(Line::from(""));
+                    content.push(Line::from(format!("Typed: {}_", typed)));
                 }
-                content.push(Line::from(options_spans));
 
                 let p = Paragraph::new(content)
                     .alignment(Alignment::Center)
@@ -166,6 +323,24 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                 f.render_widget(p, chunks[1]);
             }
         }
+        GameStatus::Paused { .. } => {
+            // The options stay hidden so the break can't be used to think
+            let text = vec![
+                Line::from(Span::styled(
+                    "PAUSED",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from("The timer has stopped."),
+                Line::from("Press Enter to carry on, or 'Esc' for the Menu"),
+            ];
+            let p = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title("Paused"));
+            f.render_widget(p, chunks[1]);
+        }
         GameStatus::Feedback { success, message } => {
             let color = if *success { Color::Green } else { Color::Red };
             let text = vec![
@@ -174,7 +349,7 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                     Style::default().fg(color).add_modifier(Modifier::BOLD),
                 )),
                 Line::from(""),
-                Line::from(message.as_str()),
+                Line::from(engine.render(message)),
             ];
             let p = Paragraph::new(text)
                 .alignment(Alignment::Center)
@@ -193,7 +368,7 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
             };
             let color = if *passed { Color::Green } else { Color::Red };
 
-            let total = if let Some(s) = &engine.session {
+            let total = if let Some(s) = engine.session() {
                 s.total_questions
             } else {
                 5
@@ -221,7 +396,7 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
             f.render_widget(p, chunks[1]);
         }
         GameStatus::SessionComplete { score } => {
-            if let Some(_session) = &engine.session {
+            if let Some(session) = engine.session() {
                 let text = vec![
                     Line::from(Span::styled(
                         "YOU DID IT!",
@@ -232,6 +407,7 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                     Line::from(""),
                     Line::from("GAME COMPLETE!"),
                     Line::from(format!("Total Score from all levels: {}", score)),
+                    Line::from(format!("Seed: {} (replay with --seed)", session.seed)),
                     Line::from(""),
                     Line::from("Press 'M' for Menu"),
                 ];
@@ -241,6 +417,51 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
                 f.render_widget(p, chunks[1]);
             }
         }
+        GameStatus::Progress => {
+            let mut text = vec![];
+            if let Some(profile) = engine.profile() {
+                let stats = &profile.stats;
+                text.push(Line::from(Span::styled(
+                    format!("Progress for {}", profile.name),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                text.push(Line::from(format!(
+                    "{} correct first time out of {} answered, {} more after trying again",
+                    stats.correct_answers, stats.questions_answered, stats.correct_after_retry
+                )));
+                text.push(Line::from(format!("Hints used: {}", stats.hints_used)));
+                text.push(Line::from(""));
+                text.push(Line::from("Trickiest characters:"));
+                for (c, s) in profile.mastery.weakest(5) {
+                    text.push(Line::from(format!(
+                        "{}: {:.0}% correct, {:.1}s average",
+                        c,
+                        s.accuracy() * 100.0,
+                        s.mean_response_time().unwrap_or(0.0)
+                    )));
+                }
+                text.push(Line::from(""));
+                text.push(Line::from("Common mix-ups:"));
+                let confusions = profile.mastery.top_confusions(5);
+                if confusions.is_empty() {
+                    text.push(Line::from("None yet!"));
+                }
+                for (target, chosen, count) in confusions {
+                    text.push(Line::from(format!(
+                        "{} mistaken for {} ({} times)",
+                        target, chosen, count
+                    )));
+                }
+            }
+            text.push(Line::from(""));
+            text.push(Line::from("Press 'Esc' to return to Menu"));
+            let p = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title("Progress"));
+            f.render_widget(p, chunks[1]);
+        }
         GameStatus::About => {
             let text = vec![
                 Line::from(Span::styled(
@@ -264,8 +485,9 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
         }
     }
 
-    let footer_text = match engine.status {
-        GameStatus::Menu => "Use Keyboard to Select",
+    let footer_text = match engine.status() {
+        GameStatus::Menu | GameStatus::ProfileSelect => "Use Keyboard to Select",
+        GameStatus::Playing | GameStatus::Feedback { .. } => "Press 'Esc' to Pause",
         _ => "Press 'Esc' to Quit",
     };
     f.render_widget(
@@ -273,3 +495,26 @@ pub fn draw(f: &mut Frame, engine: &GameEngine) {
         chunks[2],
     );
 }
+
+/// Lays `entries` out row by row in `columns` columns of `width` characters,
+/// shortening any entry that would run into its neighbour. Every row is padded
+/// to the full width so the columns stay lined up when centred.
+fn in_columns<S: AsRef<str>>(entries: &[S], columns: usize, width: usize) -> Vec<Line<'static>> {
+    entries
+        .chunks(columns)
+        .map(|row| {
+            let mut text = String::new();
+            for entry in row {
+                let entry = entry.as_ref();
+                let cell = if entry.chars().count() < width {
+                    entry.to_string()
+                } else {
+                    let cut: String = entry.chars().take(width - 2).collect();
+                    format!("{}…", cut)
+                };
+                text.push_str(&format!("{:<width$}", cell, width = width));
+            }
+            Line::from(format!("{:<width$}", text, width = columns * width))
+        })
+        .collect()
+}
@@ -1,8 +1,18 @@
-use letterlanders_core::{GameEngine, GameSettings, GameStatus, GameVariant, SoundEvent};
+use letterlanders_core::{
+    Action, EngineEvent, GameEngine, GameSettings, GameStatus, GameVariant, Profile, ProfileStore,
+};
+use profile_store::LocalStorageProfileStore;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 use wasm_bindgen::prelude::*;
 
+mod profile_store;
+
+/// Serializes maps as plain objects (not ES Maps) so mastery tables read like JSON in JS.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+}
+
 // Wrapper around the GameEngine for WASM
 #[wasm_bindgen]
 pub struct WasmGameEngine {
@@ -13,13 +23,23 @@ pub struct WasmGameEngine {
 pub struct FrontendState {
     status: GameStatus,
     session: Option<letterlanders_core::SessionState>,
+    profile: Option<Profile>,
+    /// The feedback message rendered in the player's language, so the web app
+    /// shows the same text as the TUI
+    feedback_text: Option<String>,
 }
 
 impl FrontendState {
     fn from_engine(engine: &GameEngine) -> Self {
+        let feedback_text = match engine.status() {
+            GameStatus::Feedback { message, .. } => Some(engine.render(message)),
+            _ => None,
+        };
         Self {
-            status: engine.status.clone(),
-            session: engine.session.clone(),
+            status: engine.status().clone(),
+            session: engine.session().cloned(),
+            profile: engine.profile().cloned(),
+            feedback_text,
         }
     }
 }
@@ -44,92 +64,153 @@ impl WasmGameEngine {
     pub fn get_game_state(&self) -> JsValue {
         let engine = self.engine.lock().unwrap();
         let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+        to_js(&state).unwrap()
     }
 
     pub fn get_settings(&self) -> JsValue {
         let engine = self.engine.lock().unwrap();
-        serde_wasm_bindgen::to_value(&engine.settings).unwrap()
+        to_js(engine.settings()).unwrap()
     }
 
-    pub fn update_settings(&self, settings_val: JsValue) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        let settings: GameSettings = serde_wasm_bindgen::from_value(settings_val).unwrap();
-        engine.settings = settings;
-        let _ = Self::save_settings_to_local_storage(&engine.settings);
-        engine.status = GameStatus::Menu;
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+    /// Generic entry point taking a serialized `Action`, e.g. `{ MoveSelection: 1 }`.
+    /// Invalid transitions are thrown as JS errors carrying the `EngineError` message.
+    pub fn dispatch(&self, action_val: JsValue) -> Result<JsValue, JsError> {
+        let action: Action = serde_wasm_bindgen::from_value(action_val)?;
+        self.dispatch_action(action)
     }
 
-    pub fn go_to_about(&self) -> JsValue {
+    pub fn update_settings(&self, settings_val: JsValue) -> Result<JsValue, JsError> {
         let mut engine = self.engine.lock().unwrap();
-        engine.go_to_about();
+        let settings: GameSettings = serde_wasm_bindgen::from_value(settings_val)?;
+        engine.dispatch(Action::UpdateSettings(settings))?;
+        // A profile's settings are persisted with it via `ProfileUpdated`
+        if engine.profile().is_none() {
+            let _ = Self::save_settings_to_local_storage(engine.settings());
+        }
+        // Saving from the settings screen returns to the menu
+        let _ = engine.dispatch(Action::Quit);
         let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+        Ok(to_js(&state)?)
     }
 
-    pub fn go_to_settings(&self) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        engine.status = GameStatus::Settings { message: None };
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+    pub fn go_to_about(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::OpenAbout)
     }
 
-    pub fn start_new_game(&self, variant_str: String) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        let variant = match variant_str.as_str() {
-            "Letters" => GameVariant::Letters,
-            _ => GameVariant::Numbers,
-        };
-        engine.start_game(variant);
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+    pub fn go_to_progress(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::OpenProgress)
     }
 
-    pub fn submit_answer(&self, answer: String) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        if let Some(c) = answer.chars().next() {
-            engine.submit_answer(c);
-        }
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+    pub fn go_to_settings(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::OpenSettings)
     }
 
-    pub fn next_level(&self) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        match engine.status {
-            GameStatus::LevelComplete { .. } => engine.advance_to_next_level_or_retry(),
-            GameStatus::Feedback { .. } => engine.next_level(),
-            _ => {}
+    /// Starts a session. Passing a seed replays the same questions on every platform.
+    /// The variant is serialized like `"Letters"` or `{ Custom: "this week" }`.
+    pub fn start_new_game(
+        &self,
+        variant_val: JsValue,
+        seed: Option<u32>,
+    ) -> Result<JsValue, JsError> {
+        let variant: GameVariant = serde_wasm_bindgen::from_value(variant_val)?;
+        self.engine.lock().unwrap().set_seed(seed);
+        self.dispatch_action(Action::StartGame(variant))
+    }
+
+    pub fn submit_answer(&self, answer: String) -> Result<JsValue, JsError> {
+        if answer.is_empty() {
+            return Ok(self.get_game_state());
         }
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+        self.dispatch_action(Action::Submit(answer))
     }
 
-    pub fn tick(&self, dt_seconds: f64) -> JsValue {
-        let mut engine = self.engine.lock().unwrap();
-        engine.tick(dt_seconds);
-        let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+    /// Asks for a hint on the current question, as configured in settings.
+    pub fn request_hint(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::RequestHint)
     }
 
-    pub fn consume_sound(&self) -> JsValue {
+    /// Says the current prompt again; the sounds arrive through `drain_events`.
+    pub fn replay_prompt(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::ReplayPrompt)
+    }
+
+    /// Freezes timers and hides the question, e.g. when the tab is hidden.
+    pub fn pause_game(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::Pause)
+    }
+
+    pub fn resume_game(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::Resume)
+    }
+
+    pub fn next_level(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::Continue)
+    }
+
+    pub fn tick(&self, dt_seconds: f64) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::Tick(dt_seconds))
+    }
+
+    /// Returns the engine events emitted since the last call, oldest first.
+    /// Profile updates are persisted to localStorage on the way through.
+    pub fn drain_events(&self) -> JsValue {
         let mut engine = self.engine.lock().unwrap();
-        let sound = engine.consume_sound();
-        if sound == SoundEvent::None {
-            JsValue::NULL
-        } else {
-            serde_wasm_bindgen::to_value(&sound).unwrap()
+        let events = engine.drain_events();
+        for event in &events {
+            if let EngineEvent::ProfileUpdated(profile) = event {
+                let _ = LocalStorageProfileStore.save_profile(profile);
+            }
         }
+        to_js(&events).unwrap()
+    }
+
+    pub fn list_profiles(&self) -> Result<JsValue, JsError> {
+        let profiles = LocalStorageProfileStore
+            .load_profiles()
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(to_js(&profiles)?)
+    }
+
+    /// Creates and saves a new profile, then starts playing as it.
+    pub fn create_profile(&self, name: String, avatar: String) -> Result<JsValue, JsError> {
+        let created_at = (js_sys::Date::now() / 1000.0) as u64;
+        let profile = Profile::new(&name, &avatar, created_at);
+        LocalStorageProfileStore
+            .save_profile(&profile)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        self.dispatch_action(Action::SelectProfile(profile))
+    }
+
+    pub fn select_profile(&self, id: String) -> Result<JsValue, JsError> {
+        let profile = LocalStorageProfileStore
+            .load_profiles()
+            .map_err(|e| JsError::new(&e.to_string()))?
+            .into_iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| JsError::new(&format!("no profile with id {}", id)))?;
+        self.dispatch_action(Action::SelectProfile(profile))
+    }
+
+    pub fn delete_profile(&self, id: String) -> Result<JsValue, JsError> {
+        LocalStorageProfileStore
+            .delete_profile(&id)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        self.list_profiles()
+    }
+
+    pub fn switch_profile(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::SwitchProfile)
+    }
+
+    pub fn reset_game(&self) -> Result<JsValue, JsError> {
+        self.dispatch_action(Action::Quit)
     }
 
-    pub fn reset_game(&self) -> JsValue {
+    fn dispatch_action(&self, action: Action) -> Result<JsValue, JsError> {
         let mut engine = self.engine.lock().unwrap();
-        engine.status = GameStatus::Menu;
-        engine.session = None;
+        engine.dispatch(action)?;
         let state = FrontendState::from_engine(&engine);
-        serde_wasm_bindgen::to_value(&state).unwrap()
+        Ok(to_js(&state)?)
     }
 
     // Internal helpers for LocalStorage
@@ -137,7 +218,9 @@ impl WasmGameEngine {
         let window = web_sys::window()?;
         let storage = window.local_storage().ok()??;
         let json = storage.get_item("letterlanders_settings").ok()??;
-        serde_json::from_str(&json).ok()
+        let settings: GameSettings = serde_json::from_str(&json).ok()?;
+        settings.validate().ok()?;
+        Some(settings)
     }
 
     fn save_settings_to_local_storage(settings: &GameSettings) -> Option<()> {
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Result};
+use letterlanders_core::{Profile, ProfileStore};
+use web_sys::Storage;
+
+const PROFILES_KEY: &str = "letterlanders_profiles";
+
+/// Keeps every profile as one JSON array in the browser's localStorage.
+pub struct LocalStorageProfileStore;
+
+impl LocalStorageProfileStore {
+    fn storage() -> Result<Storage> {
+        web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .ok_or_else(|| anyhow!("localStorage is not available"))
+    }
+
+    fn write_all(profiles: &[Profile]) -> Result<()> {
+        let json = serde_json::to_string(profiles)?;
+        Self::storage()?
+            .set_item(PROFILES_KEY, &json)
+            .map_err(|_| anyhow!("failed to write profiles to localStorage"))
+    }
+}
+
+impl ProfileStore for LocalStorageProfileStore {
+    fn load_profiles(&self) -> Result<Vec<Profile>> {
+        let json = Self::storage()?
+            .get_item(PROFILES_KEY)
+            .map_err(|_| anyhow!("failed to read profiles from localStorage"))?;
+        match json {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn save_profile(&mut self, profile: &Profile) -> Result<()> {
+        let mut profiles = self.load_profiles()?;
+        match profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+        Self::write_all(&profiles)
+    }
+
+    fn delete_profile(&mut self, id: &str) -> Result<()> {
+        let mut profiles = self.load_profiles()?;
+        profiles.retain(|p| p.id != id);
+        Self::write_all(&profiles)
+    }
+}